@@ -0,0 +1,167 @@
+use crate::pollers::BaselineMetric;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use super::BaselineError;
+
+/// The daily values of the baseline metrics of each person.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    persons: HashMap<String, BTreeMap<NaiveDate, HashMap<BaselineMetric, f32>>>,
+}
+
+impl History {
+    pub fn load(history_file: &str) -> Result<History, BaselineError> {
+        match fs::read_to_string(history_file) {
+            Ok(content) => serde_json::from_str(&content).map_err(|err| {
+                BaselineError::HistoryFileError(format!(
+                    "Cannot parse baseline history file '{}': {}",
+                    history_file, err
+                ))
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(BaselineError::HistoryFileError(format!(
+                "Cannot read baseline history file '{}': {}",
+                history_file, err
+            ))),
+        }
+    }
+
+    /// The file is written on the blocking thread pool, so that the receiving of the data does not
+    /// wait for the disk.
+    pub async fn save(&self, history_file: &str) -> Result<(), BaselineError> {
+        let content = serde_json::to_string(self)
+            .map_err(|err| BaselineError::HistoryFileError(err.to_string()))?;
+        let history_file = history_file.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let temporary_file = format!("{}.tmp", history_file);
+            fs::write(&temporary_file, content)
+                .and_then(|_| fs::rename(&temporary_file, &history_file))
+                .map_err(|err| {
+                    BaselineError::HistoryFileError(format!(
+                        "Cannot write baseline history file '{}': {}",
+                        history_file, err
+                    ))
+                })
+        })
+        .await
+        .map_err(|err| BaselineError::HistoryFileError(err.to_string()))?
+    }
+
+    pub fn record(
+        &mut self,
+        person_name: &str,
+        day: NaiveDate,
+        metric: BaselineMetric,
+        value: f32,
+    ) {
+        self.persons
+            .entry(person_name.to_owned())
+            .or_default()
+            .entry(day)
+            .or_default()
+            .insert(metric, value);
+    }
+
+    /// Returns the values of the `days` days before `day`.
+    pub fn values_before(
+        &self,
+        person_name: &str,
+        day: NaiveDate,
+        metric: BaselineMetric,
+        days: u32,
+    ) -> Vec<f32> {
+        match self.persons.get(person_name) {
+            Some(daily_values) => daily_values
+                .range(day - Duration::days(days.into())..day)
+                .filter_map(|(_, values)| values.get(&metric).copied())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Drops the days that are more than `days` days older than the latest day of each person.
+    pub fn prune(&mut self, days: u32) {
+        for daily_values in self.persons.values_mut() {
+            if let Some(latest_day) = daily_values.keys().next_back().copied() {
+                *daily_values = daily_values.split_off(&(latest_day - Duration::days(days.into())));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::History;
+    use crate::pollers::BaselineMetric;
+    use chrono::NaiveDate;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    #[test]
+    fn test_values_before() {
+        let mut history = History::default();
+        for (index, value) in [50.0, 52.0, 54.0, 56.0].into_iter().enumerate() {
+            history.record(
+                "person",
+                day(index as u32 + 1),
+                BaselineMetric::RestingHeartRate,
+                value,
+            );
+        }
+        history.record("person", day(3), BaselineMetric::AverageHrv, 40.0);
+
+        assert_eq!(
+            history.values_before("person", day(4), BaselineMetric::RestingHeartRate, 2),
+            vec![52.0, 54.0]
+        );
+        assert_eq!(
+            history.values_before("person", day(4), BaselineMetric::AverageHrv, 7),
+            vec![40.0]
+        );
+        assert!(history
+            .values_before("other", day(4), BaselineMetric::AverageHrv, 7)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut history = History::default();
+        for index in 1..=10 {
+            history.record("person", day(index), BaselineMetric::ReadinessScore, 80.0);
+        }
+
+        history.prune(3);
+
+        assert_eq!(
+            history.values_before("person", day(11), BaselineMetric::ReadinessScore, 90),
+            vec![80.0; 4]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load() {
+        let history_file = std::env::temp_dir().join(format!(
+            "ouraring-api-exporter-baselines-{}.json",
+            std::process::id()
+        ));
+        let history_file = history_file.to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&history_file);
+
+        assert_eq!(History::load(&history_file).unwrap(), History::default());
+
+        let mut history = History::default();
+        history.record("person", day(1), BaselineMetric::TemperatureDeviation, -0.2);
+        history.save(&history_file).await.unwrap();
+
+        let loaded = History::load(&history_file).unwrap();
+        std::fs::remove_file(&history_file).unwrap();
+
+        assert_eq!(loaded, history);
+    }
+}
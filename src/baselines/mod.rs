@@ -0,0 +1,281 @@
+mod history;
+
+use crate::config::Baselines;
+use crate::pollers::{Baseline, BaselineMetric, OuraData, SleepType};
+use chrono::NaiveDate;
+use history::History;
+use log::error;
+use thiserror::Error;
+
+pub const DEFAULT_HISTORY_FILE: &str = "baseline_history.json";
+
+const BASELINE_WINDOWS_IN_DAYS: [u32; 3] = [7, 30, 90];
+const LONGEST_WINDOW_IN_DAYS: u32 = 90;
+const MINIMUM_SAMPLES_FOR_Z_SCORE: usize = 3;
+
+#[derive(Debug, Error)]
+pub enum BaselineError {
+    #[error("{0}")]
+    HistoryFileError(String),
+}
+
+/// Keeps the daily values of the baseline metrics in a history file, so that the rolling
+/// baselines survive restarts, and compares every new daily value to them.
+pub struct BaselineTracker {
+    history_file: String,
+    history: History,
+}
+
+impl BaselineTracker {
+    pub fn from_config(config: &Baselines) -> Result<BaselineTracker, BaselineError> {
+        let history_file = config
+            .history_file
+            .clone()
+            .unwrap_or_else(|| DEFAULT_HISTORY_FILE.to_string());
+        let history = History::load(&history_file)?;
+
+        Ok(BaselineTracker {
+            history_file,
+            history,
+        })
+    }
+
+    /// Records the daily values found in `data` and appends their baselines to it. All the values
+    /// are recorded first, so a batch spanning several days gets the same baselines regardless
+    /// of its order.
+    pub async fn track(&mut self, mut data: Vec<OuraData>) -> Vec<OuraData> {
+        let daily_values: Vec<(String, NaiveDate, BaselineMetric, f32)> =
+            data.iter().flat_map(extract_daily_values).collect();
+        if daily_values.is_empty() {
+            return data;
+        }
+
+        for (person_name, day, metric, value) in &daily_values {
+            self.history.record(person_name, *day, *metric, *value);
+        }
+
+        for (person_name, day, metric, value) in &daily_values {
+            data.extend(
+                compute_baselines(&self.history, person_name, *day, *metric, *value)
+                    .into_iter()
+                    .map(OuraData::Baseline),
+            );
+        }
+
+        self.history.prune(LONGEST_WINDOW_IN_DAYS);
+        if let Err(err) = self.history.save(&self.history_file).await {
+            error!("Error saving baseline history: {}", err);
+        }
+
+        data
+    }
+}
+
+fn extract_daily_values(data: &OuraData) -> Vec<(String, NaiveDate, BaselineMetric, f32)> {
+    let values = match data {
+        // Naps and rest periods would skew the baselines, so only the main sleep is used.
+        OuraData::Sleep(sleep) if matches!(sleep.sleep_type, SleepType::LongSleep) => {
+            let daily_values = vec![
+                (
+                    BaselineMetric::RestingHeartRate,
+                    sleep.lowest_heart_rate.map(f32::from),
+                ),
+                (BaselineMetric::AverageHrv, sleep.average_hrv.map(f32::from)),
+                (BaselineMetric::RespiratoryRate, sleep.average_breath),
+            ];
+            daily_values
+                .into_iter()
+                .map(|(metric, value)| (&sleep.person_name, sleep.day, metric, value))
+                .collect()
+        }
+        OuraData::Readiness(readiness) => vec![
+            (
+                &readiness.person_name,
+                readiness.day,
+                BaselineMetric::TemperatureDeviation,
+                readiness.temperature_deviation,
+            ),
+            (
+                &readiness.person_name,
+                readiness.day,
+                BaselineMetric::ReadinessScore,
+                Some(readiness.score.into()),
+            ),
+        ],
+        _ => Vec::new(),
+    };
+
+    values
+        .into_iter()
+        .filter_map(|(person_name, day, metric, value)| {
+            value.map(|value| (person_name.to_string(), day, metric, value))
+        })
+        .collect()
+}
+
+fn compute_baselines(
+    history: &History,
+    person_name: &str,
+    day: NaiveDate,
+    metric: BaselineMetric,
+    value: f32,
+) -> Vec<Baseline> {
+    let timestamp = match day.and_hms_opt(0, 0, 0) {
+        Some(datetime) => datetime.and_utc(),
+        None => return Vec::new(),
+    };
+
+    BASELINE_WINDOWS_IN_DAYS
+        .iter()
+        .filter_map(|window_in_days| {
+            let values = history.values_before(person_name, day, metric, *window_in_days);
+            if values.is_empty() {
+                return None;
+            }
+
+            let count = values.len() as f32;
+            let mean = values.iter().sum::<f32>() / count;
+            let standard_deviation = (values
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f32>()
+                / count)
+                .sqrt();
+            let z_score = if values.len() >= MINIMUM_SAMPLES_FOR_Z_SCORE && standard_deviation > 0.0
+            {
+                Some((value - mean) / standard_deviation)
+            } else {
+                None
+            };
+
+            Some(Baseline {
+                day,
+                metric,
+                window_in_days: *window_in_days,
+                value,
+                mean,
+                standard_deviation,
+                z_score,
+                samples: values.len() as u32,
+                timestamp,
+                person_name: person_name.to_owned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compute_baselines, history::History, BaselineTracker};
+    use crate::oura_api::OuraDailyReadinessDocument;
+    use crate::pollers::{BaselineMetric, OuraData, Readiness};
+    use chrono::NaiveDate;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn readiness(day: &str, score: u8) -> Readiness {
+        OuraDailyReadinessDocument {
+            id: day.to_owned(),
+            day: day.to_owned(),
+            score: Some(score),
+            timestamp: format!("{}T00:00:00+00:00", day),
+            ..Default::default()
+        }
+        .try_to_readiness("person")
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compute_baselines() {
+        let mut history = History::default();
+        for (index, value) in [50.0, 52.0, 54.0, 56.0, 58.0].into_iter().enumerate() {
+            history.record(
+                "person",
+                day(index as u32 + 1),
+                BaselineMetric::RestingHeartRate,
+                value,
+            );
+        }
+
+        let baselines = compute_baselines(
+            &history,
+            "person",
+            day(6),
+            BaselineMetric::RestingHeartRate,
+            60.0,
+        );
+
+        assert_eq!(baselines.len(), 3);
+        assert_eq!(baselines[0].window_in_days, 7);
+        assert_eq!(baselines[0].samples, 5);
+        assert_eq!(baselines[0].mean, 54.0);
+        assert!((baselines[0].standard_deviation - 2.8284).abs() < 0.001);
+        assert!((baselines[0].z_score.unwrap() - 2.1213).abs() < 0.001);
+        assert_eq!(baselines[0].person_name, "person");
+        assert_eq!(baselines[0].timestamp.date_naive(), day(6));
+        assert_eq!(baselines[1].window_in_days, 30);
+        assert_eq!(baselines[2].window_in_days, 90);
+    }
+
+    #[test]
+    fn test_compute_baselines_without_enough_samples() {
+        let mut history = History::default();
+        history.record("person", day(1), BaselineMetric::AverageHrv, 40.0);
+        history.record("person", day(2), BaselineMetric::AverageHrv, 44.0);
+
+        let baselines =
+            compute_baselines(&history, "person", day(3), BaselineMetric::AverageHrv, 42.0);
+
+        assert_eq!(baselines[0].samples, 2);
+        assert_eq!(baselines[0].mean, 42.0);
+        assert!(baselines[0].z_score.is_none());
+        assert!(
+            compute_baselines(&history, "person", day(1), BaselineMetric::AverageHrv, 40.0)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_track_persists_history() {
+        let history_file = std::env::temp_dir().join(format!(
+            "ouraring-api-exporter-baseline-tracker-{}.json",
+            std::process::id()
+        ));
+        let history_file = history_file.to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&history_file);
+        let config = crate::config::Baselines {
+            history_file: Some(history_file.clone()),
+        };
+
+        let mut tracker = BaselineTracker::from_config(&config).unwrap();
+        let data = tracker
+            .track(vec![
+                OuraData::Readiness(readiness("2024-01-01", 80)),
+                OuraData::Readiness(readiness("2024-01-02", 84)),
+            ])
+            .await;
+        assert_eq!(data.len(), 5);
+
+        // A restarted tracker still has the earlier days in its baselines.
+        let mut tracker = BaselineTracker::from_config(&config).unwrap();
+        let data = tracker
+            .track(vec![OuraData::Readiness(readiness("2024-01-03", 88))])
+            .await;
+        std::fs::remove_file(&history_file).unwrap();
+
+        let baselines: Vec<_> = data
+            .iter()
+            .filter_map(|item| match item {
+                OuraData::Baseline(baseline) => Some(baseline),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(baselines.len(), 3);
+        assert_eq!(baselines[0].metric, BaselineMetric::ReadinessScore);
+        assert_eq!(baselines[0].value, 88.0);
+        assert_eq!(baselines[0].mean, 82.0);
+        assert_eq!(baselines[0].samples, 2);
+    }
+}
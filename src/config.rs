@@ -20,9 +20,9 @@ pub enum ConfigLogLevel {
     Trace,
 }
 
-impl Into<log::LevelFilter> for &ConfigLogLevel {
-    fn into(self) -> log::LevelFilter {
-        match self {
+impl From<&ConfigLogLevel> for log::LevelFilter {
+    fn from(val: &ConfigLogLevel) -> Self {
+        match val {
             ConfigLogLevel::Debug => log::LevelFilter::Debug,
             ConfigLogLevel::Info => log::LevelFilter::Info,
             ConfigLogLevel::Warning => log::LevelFilter::Warn,
@@ -41,10 +41,20 @@ pub enum ConfigError {
     YamlError(#[from] serde_yaml::Error),
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct OuraOAuth2 {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub state_file: Option<String>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct OuraPerson {
     pub name: String,
-    pub access_token: String,
+    pub access_token: Option<String>,
+    pub oauth2: Option<OuraOAuth2>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -53,6 +63,15 @@ pub struct InfluxDB {
     pub token: String,
     pub organization: String,
     pub bucket: String,
+    pub metadata_tags: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OuraApiRetry {
+    pub max_attempts: Option<u32>,
+    pub base_delay_ms: Option<u64>,
+    pub max_delay_ms: Option<u64>,
+    pub retryable_status_codes: Option<Vec<u16>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -61,6 +80,24 @@ pub struct OuraApi {
     pub port: Option<String>,
     pub proxy: Option<String>,
     pub verbose_logging: Option<bool>,
+    pub sandbox: Option<bool>,
+    pub timeout_seconds: Option<u64>,
+    pub retry: Option<OuraApiRetry>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Webhook {
+    pub client_id: String,
+    pub client_secret: String,
+    pub callback_url: String,
+    pub verification_token: String,
+    pub listen_address: Option<String>,
+    pub data_types: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Baselines {
+    pub history_file: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -69,6 +106,8 @@ pub struct Config {
     pub poller_interval: u16,
     pub influxdb: Option<InfluxDB>,
     pub oura_api: Option<OuraApi>,
+    pub webhook: Option<Webhook>,
+    pub baselines: Option<Baselines>,
     pub log_level: Option<ConfigLogLevel>,
 }
 
@@ -80,5 +119,5 @@ pub fn get_config() -> Result<Config, ConfigError> {
     let config_file = File::open(&configuration_file_path)
         .map_err(|err| ConfigError::FileError(err, configuration_file_path))?;
 
-    return from_reader(config_file).map_err(ConfigError::YamlError);
+    from_reader(config_file).map_err(ConfigError::YamlError)
 }
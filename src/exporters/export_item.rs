@@ -1,5 +1,10 @@
 use crate::exporters::influx_db_measurement::{InfluxDBMeasurement, MeasurementConvertingError};
-use crate::pollers::{HeartRate, HeartRateVariability, OuraData, Readiness, Sleep, SleepPhase};
+use crate::pollers::{
+    Activity, ActivityClass, Baseline, BedtimeRecommendation, CardiovascularAge, DailySleepScore,
+    HeartRate, HeartRateVariability, Met, OuraData, PersonMetadata, Readiness, Resilience,
+    RestModePeriod, Session, SessionSample, Sleep, SleepArchitecture, SleepMovement, SleepPhase,
+    SleepPhaseSegment, SleepSeriesSummary, SpO2, Stress, Tag, VO2Max, Workout,
+};
 use std::fmt;
 use thiserror::Error;
 
@@ -9,6 +14,7 @@ pub enum MqttTopic {
     Sleep,
     Activity,
     Readiness,
+    Tag,
 }
 
 #[derive(Error, Debug)]
@@ -31,7 +37,7 @@ where
         .try_into()
         .map_err(ExportItemGenerationError::InfluxDBItemGenerationError)?;
 
-    return Ok(ExportItem::InfluxDB(influx_db_measurement));
+    Ok(ExportItem::InfluxDB(influx_db_measurement))
 }
 
 impl TryFrom<&HeartRate> for Vec<ExportItem> {
@@ -41,13 +47,13 @@ impl TryFrom<&HeartRate> for Vec<ExportItem> {
         let mqtt_payload = serde_json::to_string(heart_rate_data)
             .map_err(ExportItemGenerationError::MQTTMessageSerializationError)?;
 
-        return Ok(vec![
+        Ok(vec![
             ExportItem::MQTT(MqttMessage {
                 topic: MqttTopic::HeartRate,
                 payload: mqtt_payload,
             }),
             try_into_influx_db_export_item(heart_rate_data)?,
-        ]);
+        ])
     }
 }
 
@@ -57,7 +63,7 @@ impl TryFrom<&HeartRateVariability> for Vec<ExportItem> {
     fn try_from(
         hrv_data: &HeartRateVariability,
     ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
-        return Ok(vec![try_into_influx_db_export_item(hrv_data)?]);
+        Ok(vec![try_into_influx_db_export_item(hrv_data)?])
     }
 }
 
@@ -67,7 +73,7 @@ impl TryFrom<&Sleep> for Vec<ExportItem> {
     fn try_from(sleep_data: &Sleep) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
         let export_item = try_into_influx_db_export_item(sleep_data)?;
 
-        return Ok(vec![export_item]);
+        Ok(vec![export_item])
     }
 }
 
@@ -75,7 +81,73 @@ impl TryFrom<&SleepPhase> for Vec<ExportItem> {
     type Error = ExportItemGenerationError;
 
     fn try_from(sleep_phase: &SleepPhase) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
-        return Ok(vec![try_into_influx_db_export_item(sleep_phase)?]);
+        Ok(vec![try_into_influx_db_export_item(sleep_phase)?])
+    }
+}
+
+impl TryFrom<&SleepPhaseSegment> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        sleep_phase_segment: &SleepPhaseSegment,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(sleep_phase_segment)?])
+    }
+}
+
+impl TryFrom<&SleepArchitecture> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        sleep_architecture: &SleepArchitecture,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(sleep_architecture)?])
+    }
+}
+
+impl TryFrom<&Baseline> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(baseline: &Baseline) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(baseline)?])
+    }
+}
+
+impl TryFrom<&PersonMetadata> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        person_metadata: &PersonMetadata,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        // A line without any fields is rejected by InfluxDB.
+        if person_metadata.age.is_none()
+            && person_metadata.weight.is_none()
+            && person_metadata.height.is_none()
+        {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![try_into_influx_db_export_item(person_metadata)?])
+    }
+}
+
+impl TryFrom<&SleepSeriesSummary> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        sleep_series_summary: &SleepSeriesSummary,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(sleep_series_summary)?])
+    }
+}
+
+impl TryFrom<&SleepMovement> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        sleep_movement: &SleepMovement,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(sleep_movement)?])
     }
 }
 
@@ -83,7 +155,159 @@ impl TryFrom<&Readiness> for Vec<ExportItem> {
     type Error = ExportItemGenerationError;
 
     fn try_from(readiness: &Readiness) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
-        return Ok(vec![try_into_influx_db_export_item(readiness)?]);
+        Ok(vec![try_into_influx_db_export_item(readiness)?])
+    }
+}
+
+impl TryFrom<&Activity> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(activity: &Activity) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        let mqtt_payload = serde_json::to_string(activity)
+            .map_err(ExportItemGenerationError::MQTTMessageSerializationError)?;
+
+        Ok(vec![
+            ExportItem::MQTT(MqttMessage {
+                topic: MqttTopic::Activity,
+                payload: mqtt_payload,
+            }),
+            try_into_influx_db_export_item(activity)?,
+        ])
+    }
+}
+
+impl TryFrom<&ActivityClass> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        activity_class: &ActivityClass,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(activity_class)?])
+    }
+}
+
+impl TryFrom<&Met> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(met: &Met) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(met)?])
+    }
+}
+
+impl TryFrom<&DailySleepScore> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        daily_sleep_score: &DailySleepScore,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(daily_sleep_score)?])
+    }
+}
+
+impl TryFrom<&Workout> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(workout: &Workout) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(workout)?])
+    }
+}
+
+impl TryFrom<&Session> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(session: &Session) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(session)?])
+    }
+}
+
+impl TryFrom<&SessionSample> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        session_sample: &SessionSample,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(session_sample)?])
+    }
+}
+
+impl TryFrom<&SpO2> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(spo2: &SpO2) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(spo2)?])
+    }
+}
+
+impl TryFrom<&Stress> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(stress: &Stress) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(stress)?])
+    }
+}
+
+impl TryFrom<&Resilience> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(resilience: &Resilience) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(resilience)?])
+    }
+}
+
+impl TryFrom<&CardiovascularAge> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        cardiovascular_age: &CardiovascularAge,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(cardiovascular_age)?])
+    }
+}
+
+impl TryFrom<&VO2Max> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(vo2_max: &VO2Max) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(vo2_max)?])
+    }
+}
+
+impl TryFrom<&Tag> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(tag: &Tag) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        let mqtt_payload = serde_json::to_string(tag)
+            .map_err(ExportItemGenerationError::MQTTMessageSerializationError)?;
+
+        Ok(vec![
+            ExportItem::MQTT(MqttMessage {
+                topic: MqttTopic::Tag,
+                payload: mqtt_payload,
+            }),
+            try_into_influx_db_export_item(tag)?,
+        ])
+    }
+}
+
+impl TryFrom<&RestModePeriod> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        rest_mode_period: &RestModePeriod,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(rest_mode_period)?])
+    }
+}
+
+impl TryFrom<&BedtimeRecommendation> for Vec<ExportItem> {
+    type Error = ExportItemGenerationError;
+
+    fn try_from(
+        bedtime_recommendation: &BedtimeRecommendation,
+    ) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
+        Ok(vec![try_into_influx_db_export_item(
+            bedtime_recommendation,
+        )?])
     }
 }
 
@@ -94,6 +318,7 @@ impl fmt::Display for MqttTopic {
             MqttTopic::Sleep => write!(f, "oura/sleep"),
             MqttTopic::Activity => write!(f, "oura/activity"),
             MqttTopic::Readiness => write!(f, "oura/readiness"),
+            MqttTopic::Tag => write!(f, "oura/tag"),
         }
     }
 }
@@ -104,6 +329,7 @@ pub struct MqttMessage {
     pub payload: String,
 }
 
+#[allow(clippy::large_enum_variant, clippy::upper_case_acronyms)]
 pub enum ExportItem {
     MQTT(MqttMessage),
     InfluxDB(InfluxDBMeasurement),
@@ -113,16 +339,40 @@ impl TryFrom<&OuraData> for Vec<ExportItem> {
     type Error = ExportItemGenerationError;
 
     fn try_from(oura_data: &OuraData) -> Result<Vec<ExportItem>, ExportItemGenerationError> {
-        return match oura_data {
+        match oura_data {
             OuraData::HeartRate(heart_rate_data) => Ok(heart_rate_data.try_into()?),
             OuraData::HeartRateVariability(hrv) => Ok(hrv.try_into()?),
             OuraData::Sleep(sleep) => Ok(sleep.try_into()?),
             OuraData::SleepPhase(sleep_phase) => Ok(sleep_phase.try_into()?),
-            OuraData::Activity => Ok(vec![]),
+            OuraData::SleepPhaseSegment(sleep_phase_segment) => Ok(sleep_phase_segment.try_into()?),
+            OuraData::SleepArchitecture(sleep_architecture) => Ok(sleep_architecture.try_into()?),
+            OuraData::SleepSeriesSummary(sleep_series_summary) => {
+                Ok(sleep_series_summary.try_into()?)
+            }
+            OuraData::SleepMovement(sleep_movement) => Ok(sleep_movement.try_into()?),
+            OuraData::Activity(activity) => Ok(activity.try_into()?),
+            OuraData::ActivityClass(activity_class) => Ok(activity_class.try_into()?),
+            OuraData::Met(met) => Ok(met.try_into()?),
             OuraData::Readiness(readiness) => Ok(readiness.try_into()?),
+            OuraData::DailySleepScore(daily_sleep_score) => Ok(daily_sleep_score.try_into()?),
+            OuraData::Workout(workout) => Ok(workout.try_into()?),
+            OuraData::Session(session) => Ok(session.try_into()?),
+            OuraData::SessionSample(session_sample) => Ok(session_sample.try_into()?),
+            OuraData::SpO2(spo2) => Ok(spo2.try_into()?),
+            OuraData::Stress(stress) => Ok(stress.try_into()?),
+            OuraData::Resilience(resilience) => Ok(resilience.try_into()?),
+            OuraData::CardiovascularAge(cardiovascular_age) => Ok(cardiovascular_age.try_into()?),
+            OuraData::VO2Max(vo2_max) => Ok(vo2_max.try_into()?),
+            OuraData::Tag(tag) => Ok(tag.try_into()?),
+            OuraData::RestModePeriod(rest_mode_period) => Ok(rest_mode_period.try_into()?),
+            OuraData::PersonMetadata(person_metadata) => Ok(person_metadata.try_into()?),
+            OuraData::BedtimeRecommendation(bedtime_recommendation) => {
+                Ok(bedtime_recommendation.try_into()?)
+            }
+            OuraData::Baseline(baseline) => Ok(baseline.try_into()?),
             OuraData::Error { message } => Err(ExportItemGenerationError::InvalidOuraData(
                 message.to_string(),
             )),
-        };
+        }
     }
 }
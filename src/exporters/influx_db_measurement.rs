@@ -1,9 +1,33 @@
+use crate::pollers::Activity;
+use crate::pollers::ActivityClass;
+use crate::pollers::ActivityClassType;
+use crate::pollers::Baseline;
+use crate::pollers::BedtimeRecommendation;
+use crate::pollers::CardiovascularAge;
+use crate::pollers::DailySleepScore;
 use crate::pollers::HeartRate;
 use crate::pollers::HeartRateVariability;
+use crate::pollers::Met;
+use crate::pollers::PersonMetadata;
 use crate::pollers::Readiness;
+use crate::pollers::Resilience;
+use crate::pollers::RestModePeriod;
+use crate::pollers::Session;
+use crate::pollers::SessionSample;
+use crate::pollers::SessionSampleType;
 use crate::pollers::Sleep;
+use crate::pollers::SleepArchitecture;
+use crate::pollers::SleepMovement;
 use crate::pollers::SleepPhase;
+use crate::pollers::SleepPhaseSegment;
 use crate::pollers::SleepPhaseType;
+use crate::pollers::SleepSeriesSummary;
+use crate::pollers::SleepSeriesType;
+use crate::pollers::SpO2;
+use crate::pollers::Stress;
+use crate::pollers::Tag;
+use crate::pollers::VO2Max;
+use crate::pollers::Workout;
 use influxdb2::models::WriteDataPoint;
 use influxdb2_derive::WriteDataPoint;
 use thiserror::Error;
@@ -46,6 +70,179 @@ pub struct SleepPhaseDataPoint {
     sleep_id: String,
 }
 
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "sleep_phase_segment"]
+pub struct SleepPhaseSegmentDataPoint {
+    #[influxdb(field)]
+    phase: i64,
+
+    #[influxdb(field)]
+    duration: i64,
+
+    #[influxdb(field)]
+    ordinal: i64,
+
+    #[influxdb(field)]
+    end_timestamp: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+
+    #[influxdb(tag)]
+    sleep_id: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "sleep_architecture"]
+pub struct SleepArchitectureDataPoint {
+    #[influxdb(field)]
+    cycles: i64,
+
+    #[influxdb(field)]
+    time_to_first_deep_sleep: Option<i64>,
+
+    #[influxdb(field)]
+    time_to_first_rem_sleep: Option<i64>,
+
+    #[influxdb(field)]
+    awakenings: i64,
+
+    #[influxdb(field)]
+    wake_after_sleep_onset: i64,
+
+    #[influxdb(field)]
+    rem_sleep_fraction_first_third: f64,
+
+    #[influxdb(field)]
+    rem_sleep_fraction_second_third: f64,
+
+    #[influxdb(field)]
+    rem_sleep_fraction_last_third: f64,
+
+    #[influxdb(field)]
+    deep_sleep_fraction_first_third: f64,
+
+    #[influxdb(field)]
+    deep_sleep_fraction_second_third: f64,
+
+    #[influxdb(field)]
+    deep_sleep_fraction_last_third: f64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+
+    #[influxdb(tag)]
+    sleep_id: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "sleep_hr_summary"]
+pub struct SleepHeartRateSummaryDataPoint {
+    #[influxdb(field)]
+    min: f64,
+
+    #[influxdb(field)]
+    max: f64,
+
+    #[influxdb(field)]
+    mean: f64,
+
+    #[influxdb(field)]
+    median: f64,
+
+    #[influxdb(field)]
+    p10: f64,
+
+    #[influxdb(field)]
+    p90: f64,
+
+    #[influxdb(field)]
+    standard_deviation: f64,
+
+    #[influxdb(field)]
+    nadir_offset: i64,
+
+    #[influxdb(field)]
+    samples: i64,
+
+    #[influxdb(field)]
+    null_samples: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+
+    #[influxdb(tag)]
+    sleep_id: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "sleep_hrv_summary"]
+pub struct SleepHeartRateVariabilitySummaryDataPoint {
+    #[influxdb(field)]
+    min: f64,
+
+    #[influxdb(field)]
+    max: f64,
+
+    #[influxdb(field)]
+    mean: f64,
+
+    #[influxdb(field)]
+    median: f64,
+
+    #[influxdb(field)]
+    p10: f64,
+
+    #[influxdb(field)]
+    p90: f64,
+
+    #[influxdb(field)]
+    standard_deviation: f64,
+
+    #[influxdb(field)]
+    nadir_offset: i64,
+
+    #[influxdb(field)]
+    samples: i64,
+
+    #[influxdb(field)]
+    null_samples: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+
+    #[influxdb(tag)]
+    sleep_id: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "sleep_movement"]
+pub struct SleepMovementDataPoint {
+    #[influxdb(field)]
+    movement: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+
+    #[influxdb(tag)]
+    sleep_id: String,
+}
+
 #[derive(Debug, Default, WriteDataPoint)]
 #[measurement = "sleep"]
 pub struct SleepDataPoint {
@@ -141,28 +338,533 @@ pub struct ReadinessDataPoint {
     temperature_trend_deviation: Option<f64>,
 
     #[influxdb(field)]
-    activity_balance_contribution: i64,
+    activity_balance_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    body_temperature_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    hrv_balance_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    previous_day_activity_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    previous_night_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    recovery_index_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    resting_heart_rate_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    sleep_balance_contribution: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "activity"]
+pub struct ActivityDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(field)]
+    score: Option<i64>,
+
+    #[influxdb(field)]
+    steps: i64,
+
+    #[influxdb(field)]
+    active_calories: i64,
+
+    #[influxdb(field)]
+    total_calories: i64,
+
+    #[influxdb(field)]
+    target_calories: i64,
+
+    #[influxdb(field)]
+    equivalent_walking_distance: i64,
+
+    #[influxdb(field)]
+    average_met_minutes: f64,
+
+    #[influxdb(field)]
+    high_activity_met_minutes: i64,
+
+    #[influxdb(field)]
+    medium_activity_met_minutes: i64,
+
+    #[influxdb(field)]
+    low_activity_met_minutes: i64,
+
+    #[influxdb(field)]
+    sedentary_met_minutes: i64,
+
+    #[influxdb(field)]
+    high_activity_time: i64,
+
+    #[influxdb(field)]
+    medium_activity_time: i64,
+
+    #[influxdb(field)]
+    low_activity_time: i64,
+
+    #[influxdb(field)]
+    sedentary_time: i64,
+
+    #[influxdb(field)]
+    resting_time: i64,
+
+    #[influxdb(field)]
+    non_wear_time: i64,
+
+    #[influxdb(field)]
+    inactivity_alerts: i64,
+
+    #[influxdb(field)]
+    meet_daily_targets_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    move_every_hour_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    recovery_time_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    stay_active_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    training_frequency_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    training_volume_contribution: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "activity_class"]
+pub struct ActivityClassDataPoint {
+    #[influxdb(field)]
+    class: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "met"]
+pub struct MetDataPoint {
+    #[influxdb(field)]
+    met: f64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "daily_sleep"]
+pub struct DailySleepDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(field)]
+    score: Option<i64>,
+
+    #[influxdb(field)]
+    deep_sleep_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    efficiency_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    latency_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    rem_sleep_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    restfulness_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    timing_contribution: Option<i64>,
+
+    #[influxdb(field)]
+    total_sleep_contribution: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "workout"]
+pub struct WorkoutDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    activity: String,
+
+    #[influxdb(tag)]
+    intensity: String,
+
+    #[influxdb(tag)]
+    source: String,
+
+    #[influxdb(field)]
+    duration: i64,
+
+    #[influxdb(field)]
+    calories: Option<f64>,
+
+    #[influxdb(field)]
+    distance: Option<f64>,
+
+    #[influxdb(field)]
+    label: Option<String>,
+
+    #[influxdb(field)]
+    end_datetime: i64,
+
+    #[influxdb(timestamp)]
+    start_datetime: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "session"]
+pub struct SessionDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    session_type: String,
+
+    #[influxdb(field)]
+    mood: Option<String>,
+
+    #[influxdb(field)]
+    duration: i64,
+
+    #[influxdb(field)]
+    end_datetime: i64,
+
+    #[influxdb(timestamp)]
+    start_datetime: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "session_heart_rate"]
+pub struct SessionHeartRateDataPoint {
+    #[influxdb(field)]
+    bpm: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    session_id: String,
+
+    #[influxdb(tag)]
+    session_type: String,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "session_heart_rate_variability"]
+pub struct SessionHeartRateVariabilityDataPoint {
+    #[influxdb(field)]
+    ms: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    session_id: String,
+
+    #[influxdb(tag)]
+    session_type: String,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "session_motion_count"]
+pub struct SessionMotionCountDataPoint {
+    #[influxdb(field)]
+    motion_count: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    session_id: String,
+
+    #[influxdb(tag)]
+    session_type: String,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "spo2"]
+pub struct SpO2DataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(field)]
+    average_spo2_percentage: Option<f64>,
+
+    #[influxdb(field)]
+    breathing_disturbance_index: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "stress"]
+pub struct StressDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    day_summary: String,
+
+    #[influxdb(field)]
+    stress_high: Option<i64>,
+
+    #[influxdb(field)]
+    recovery_high: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "resilience"]
+pub struct ResilienceDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    level: String,
+
+    #[influxdb(field)]
+    sleep_recovery_contribution: f64,
+
+    #[influxdb(field)]
+    daytime_recovery_contribution: f64,
+
+    #[influxdb(field)]
+    stress_contribution: f64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "cardiovascular_age"]
+pub struct CardiovascularAgeDataPoint {
+    #[influxdb(field)]
+    vascular_age: Option<i64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "vo2_max"]
+pub struct VO2MaxDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(field)]
+    vo2_max: Option<f64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "oura_tag"]
+pub struct TagDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    tag_type: String,
+
+    #[influxdb(tag)]
+    source: String,
+
+    #[influxdb(field)]
+    comment: Option<String>,
+
+    #[influxdb(field)]
+    duration: i64,
+
+    #[influxdb(field)]
+    end_datetime: i64,
+
+    #[influxdb(timestamp)]
+    start_datetime: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "rest_mode_period"]
+pub struct RestModePeriodDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(field)]
+    ongoing: bool,
+
+    #[influxdb(field)]
+    duration: Option<i64>,
+
+    #[influxdb(field)]
+    start_day: i64,
+
+    #[influxdb(field)]
+    end_day: Option<i64>,
+
+    #[influxdb(field)]
+    end_datetime: Option<i64>,
+
+    #[influxdb(field)]
+    episode_count: i64,
+
+    #[influxdb(timestamp)]
+    start_datetime: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "baseline"]
+pub struct BaselineDataPoint {
+    #[influxdb(tag)]
+    metric: String,
+
+    #[influxdb(tag)]
+    window: String,
+
+    #[influxdb(field)]
+    value: f64,
+
+    #[influxdb(field)]
+    mean: f64,
+
+    #[influxdb(field)]
+    standard_deviation: f64,
+
+    #[influxdb(field)]
+    z_score: Option<f64>,
+
+    #[influxdb(field)]
+    samples: i64,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "person_metadata"]
+pub struct PersonMetadataDataPoint {
+    #[influxdb(field)]
+    age: Option<i64>,
 
     #[influxdb(field)]
-    body_temperature_contribution: i64,
+    weight: Option<f64>,
 
     #[influxdb(field)]
-    hrv_balance_contribution: i64,
+    height: Option<f64>,
+
+    #[influxdb(timestamp)]
+    timestamp: i64,
+
+    #[influxdb(tag)]
+    person_name: String,
+}
+
+#[derive(Debug, Default, WriteDataPoint)]
+#[measurement = "bedtime_recommendation"]
+pub struct BedtimeRecommendationDataPoint {
+    #[influxdb(tag)]
+    id: String,
+
+    #[influxdb(tag)]
+    recommendation: String,
+
+    #[influxdb(tag)]
+    status: String,
 
     #[influxdb(field)]
-    previous_day_activity_contribution: i64,
+    has_optimal_bedtime: bool,
 
     #[influxdb(field)]
-    previous_night_contribution: i64,
+    optimal_bedtime_start: Option<i64>,
 
     #[influxdb(field)]
-    recovery_index_contribution: i64,
+    optimal_bedtime_end: Option<i64>,
 
     #[influxdb(field)]
-    resting_heart_rate_contribution: i64,
+    optimal_bedtime_start_offset: Option<i64>,
 
     #[influxdb(field)]
-    sleep_balance_contribution: i64,
+    optimal_bedtime_end_offset: Option<i64>,
 
     #[influxdb(timestamp)]
     timestamp: i64,
@@ -175,9 +877,33 @@ pub struct ReadinessDataPoint {
 pub enum InfluxDBMeasurement {
     HeartRate(HeartRateDataPoint),
     SleepPhase(SleepPhaseDataPoint),
+    SleepPhaseSegment(SleepPhaseSegmentDataPoint),
+    SleepArchitecture(SleepArchitectureDataPoint),
+    SleepHeartRateSummary(SleepHeartRateSummaryDataPoint),
+    SleepHeartRateVariabilitySummary(SleepHeartRateVariabilitySummaryDataPoint),
+    SleepMovement(SleepMovementDataPoint),
     Sleep(SleepDataPoint),
     HeartRateVariability(HeartRateVariabilityDataPoint),
     Readiness(ReadinessDataPoint),
+    Activity(ActivityDataPoint),
+    ActivityClass(ActivityClassDataPoint),
+    Met(MetDataPoint),
+    DailySleep(DailySleepDataPoint),
+    Workout(WorkoutDataPoint),
+    Session(SessionDataPoint),
+    SessionHeartRate(SessionHeartRateDataPoint),
+    SessionHeartRateVariability(SessionHeartRateVariabilityDataPoint),
+    SessionMotionCount(SessionMotionCountDataPoint),
+    SpO2(SpO2DataPoint),
+    Stress(StressDataPoint),
+    Resilience(ResilienceDataPoint),
+    CardiovascularAge(CardiovascularAgeDataPoint),
+    VO2Max(VO2MaxDataPoint),
+    Tag(TagDataPoint),
+    RestModePeriod(RestModePeriodDataPoint),
+    BedtimeRecommendation(BedtimeRecommendationDataPoint),
+    Baseline(BaselineDataPoint),
+    PersonMetadata(PersonMetadataDataPoint),
 }
 
 impl WriteDataPoint for InfluxDBMeasurement {
@@ -188,10 +914,101 @@ impl WriteDataPoint for InfluxDBMeasurement {
         match self {
             InfluxDBMeasurement::HeartRate(data) => data.write_data_point_to(w),
             InfluxDBMeasurement::SleepPhase(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SleepPhaseSegment(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SleepArchitecture(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SleepHeartRateSummary(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SleepHeartRateVariabilitySummary(data) => {
+                data.write_data_point_to(w)
+            }
+            InfluxDBMeasurement::SleepMovement(data) => data.write_data_point_to(w),
             InfluxDBMeasurement::Sleep(data) => data.write_data_point_to(w),
             InfluxDBMeasurement::HeartRateVariability(data) => data.write_data_point_to(w),
             InfluxDBMeasurement::Readiness(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Activity(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::ActivityClass(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Met(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::DailySleep(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Workout(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Session(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SessionHeartRate(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SessionHeartRateVariability(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SessionMotionCount(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::SpO2(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Stress(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Resilience(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::CardiovascularAge(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::VO2Max(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Tag(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::RestModePeriod(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::BedtimeRecommendation(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::Baseline(data) => data.write_data_point_to(w),
+            InfluxDBMeasurement::PersonMetadata(data) => data.write_data_point_to(w),
+        }
+    }
+}
+
+/// A measurement together with tags that are not part of the measurement itself, such as the
+/// metadata of the person the measurement belongs to.
+#[derive(Debug)]
+pub struct TaggedMeasurement {
+    pub measurement: InfluxDBMeasurement,
+    pub extra_tags: Vec<(&'static str, String)>,
+}
+
+/// The backslash is escaped first, so that a value ending in one does not escape the separator
+/// that follows it.
+fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+/// Finds the first unescaped space of a line, which separates the measurement and its tags from
+/// the fields.
+fn find_end_of_tag_set(line: &[u8]) -> Option<usize> {
+    let mut escaped = false;
+
+    for (index, byte) in line.iter().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b' ' => return Some(index),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+impl WriteDataPoint for TaggedMeasurement {
+    fn write_data_point_to<W>(&self, mut w: W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        if self.extra_tags.is_empty() {
+            return self.measurement.write_data_point_to(w);
         }
+
+        let mut line: Vec<u8> = Vec::new();
+        self.measurement.write_data_point_to(&mut line)?;
+
+        let end_of_tag_set = find_end_of_tag_set(&line).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Measurement line has no field set",
+            )
+        })?;
+        let extra_tags: String = self
+            .extra_tags
+            .iter()
+            .map(|(key, value)| format!(",{}={}", key, escape_tag_value(value)))
+            .collect();
+
+        w.write_all(&line[..end_of_tag_set])?;
+        w.write_all(extra_tags.as_bytes())?;
+        w.write_all(&line[end_of_tag_set..])
     }
 }
 
@@ -229,7 +1046,7 @@ impl TryFrom<&Sleep> for InfluxDBMeasurement {
             awake_time: value.awake_time.into(),
             bedtime_end: value.bedtime_end.timestamp(),
             bedtime_start: value.bedtime_start.timestamp(),
-            day: day?.timestamp(),
+            day: day?.and_utc().timestamp(),
             deep_sleep_duration: value.deep_sleep_duration.map(|v| v.into()),
             efficiency: value.efficiency.map(|v| v.into()),
             latency: value.latency.map(|v| v.into()),
@@ -290,6 +1107,113 @@ impl TryFrom<&SleepPhase> for InfluxDBMeasurement {
     }
 }
 
+impl TryFrom<&SleepPhaseSegment> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &SleepPhaseSegment,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::SleepPhaseSegment(
+            SleepPhaseSegmentDataPoint {
+                person_name: value.person_name.to_string(),
+                phase: (&value.sleep_phase).into(),
+                duration: value.duration_in_seconds(),
+                ordinal: value.ordinal.into(),
+                end_timestamp: value.end_datetime.timestamp(),
+                timestamp: value.start_datetime.timestamp(),
+                sleep_id: value.sleep_id.to_string(),
+            },
+        ))
+    }
+}
+
+impl TryFrom<&SleepArchitecture> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &SleepArchitecture,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::SleepArchitecture(
+            SleepArchitectureDataPoint {
+                cycles: value.cycles.into(),
+                time_to_first_deep_sleep: value.time_to_first_deep_sleep,
+                time_to_first_rem_sleep: value.time_to_first_rem_sleep,
+                awakenings: value.awakenings.into(),
+                wake_after_sleep_onset: value.wake_after_sleep_onset,
+                rem_sleep_fraction_first_third: value.rem_sleep_fractions[0].into(),
+                rem_sleep_fraction_second_third: value.rem_sleep_fractions[1].into(),
+                rem_sleep_fraction_last_third: value.rem_sleep_fractions[2].into(),
+                deep_sleep_fraction_first_third: value.deep_sleep_fractions[0].into(),
+                deep_sleep_fraction_second_third: value.deep_sleep_fractions[1].into(),
+                deep_sleep_fraction_last_third: value.deep_sleep_fractions[2].into(),
+                timestamp: value.timestamp.timestamp(),
+                person_name: value.person_name.to_string(),
+                sleep_id: value.sleep_id.to_string(),
+            },
+        ))
+    }
+}
+
+impl TryFrom<&SleepSeriesSummary> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &SleepSeriesSummary,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(match value.series_type {
+            SleepSeriesType::HeartRate => {
+                InfluxDBMeasurement::SleepHeartRateSummary(SleepHeartRateSummaryDataPoint {
+                    min: value.min.into(),
+                    max: value.max.into(),
+                    mean: value.mean.into(),
+                    median: value.median.into(),
+                    p10: value.p10.into(),
+                    p90: value.p90.into(),
+                    standard_deviation: value.standard_deviation.into(),
+                    nadir_offset: value.nadir_offset_in_seconds,
+                    samples: value.samples.into(),
+                    null_samples: value.null_samples.into(),
+                    timestamp: value.timestamp.timestamp(),
+                    person_name: value.person_name.to_string(),
+                    sleep_id: value.sleep_id.to_string(),
+                })
+            }
+            SleepSeriesType::HeartRateVariability => {
+                InfluxDBMeasurement::SleepHeartRateVariabilitySummary(
+                    SleepHeartRateVariabilitySummaryDataPoint {
+                        min: value.min.into(),
+                        max: value.max.into(),
+                        mean: value.mean.into(),
+                        median: value.median.into(),
+                        p10: value.p10.into(),
+                        p90: value.p90.into(),
+                        standard_deviation: value.standard_deviation.into(),
+                        nadir_offset: value.nadir_offset_in_seconds,
+                        samples: value.samples.into(),
+                        null_samples: value.null_samples.into(),
+                        timestamp: value.timestamp.timestamp(),
+                        person_name: value.person_name.to_string(),
+                        sleep_id: value.sleep_id.to_string(),
+                    },
+                )
+            }
+        })
+    }
+}
+
+impl TryFrom<&SleepMovement> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &SleepMovement) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::SleepMovement(SleepMovementDataPoint {
+            person_name: value.person_name.to_string(),
+            movement: value.movement.into(),
+            timestamp: value.timestamp.timestamp(),
+            sleep_id: value.sleep_id.to_string(),
+        }))
+    }
+}
+
 impl TryFrom<&Readiness> for InfluxDBMeasurement {
     type Error = MeasurementConvertingError;
 
@@ -298,16 +1222,482 @@ impl TryFrom<&Readiness> for InfluxDBMeasurement {
             readiness_score: value.score.into(),
             temperature_deviation: value.temperature_deviation.map(|v| v.into()),
             temperature_trend_deviation: value.temperature_trend_deviation.map(|v| v.into()),
-            activity_balance_contribution: value.contributors.activity_balance.into(),
-            body_temperature_contribution: value.contributors.body_temperature.into(),
-            hrv_balance_contribution: value.contributors.hrv_balance.into(),
-            previous_day_activity_contribution: value.contributors.previous_day_activity.into(),
-            previous_night_contribution: value.contributors.previous_night.into(),
-            recovery_index_contribution: value.contributors.recovery_index.into(),
-            resting_heart_rate_contribution: value.contributors.resting_heart_rate.into(),
-            sleep_balance_contribution: value.contributors.sleep_balance.into(),
+            activity_balance_contribution: value.contributors.activity_balance.map(|v| v.into()),
+            body_temperature_contribution: value.contributors.body_temperature.map(|v| v.into()),
+            hrv_balance_contribution: value.contributors.hrv_balance.map(|v| v.into()),
+            previous_day_activity_contribution: value
+                .contributors
+                .previous_day_activity
+                .map(|v| v.into()),
+            previous_night_contribution: value.contributors.previous_night.map(|v| v.into()),
+            recovery_index_contribution: value.contributors.recovery_index.map(|v| v.into()),
+            resting_heart_rate_contribution: value
+                .contributors
+                .resting_heart_rate
+                .map(|v| v.into()),
+            sleep_balance_contribution: value.contributors.sleep_balance.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Activity> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Activity) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        let contributors = &value.contributors;
+
+        Ok(InfluxDBMeasurement::Activity(ActivityDataPoint {
+            id: value.id.to_string(),
+            score: value.score.map(|v| v.into()),
+            steps: value.steps.into(),
+            active_calories: value.active_calories.into(),
+            total_calories: value.total_calories.into(),
+            target_calories: value.target_calories.into(),
+            equivalent_walking_distance: value.equivalent_walking_distance.into(),
+            average_met_minutes: value.average_met_minutes.into(),
+            high_activity_met_minutes: value.high_activity_met_minutes.into(),
+            medium_activity_met_minutes: value.medium_activity_met_minutes.into(),
+            low_activity_met_minutes: value.low_activity_met_minutes.into(),
+            sedentary_met_minutes: value.sedentary_met_minutes.into(),
+            high_activity_time: value.high_activity_time.into(),
+            medium_activity_time: value.medium_activity_time.into(),
+            low_activity_time: value.low_activity_time.into(),
+            sedentary_time: value.sedentary_time.into(),
+            resting_time: value.resting_time.into(),
+            non_wear_time: value.non_wear_time.into(),
+            inactivity_alerts: value.inactivity_alerts.into(),
+            meet_daily_targets_contribution: contributors.meet_daily_targets.map(|v| v.into()),
+            move_every_hour_contribution: contributors.move_every_hour.map(|v| v.into()),
+            recovery_time_contribution: contributors.recovery_time.map(|v| v.into()),
+            stay_active_contribution: contributors.stay_active.map(|v| v.into()),
+            training_frequency_contribution: contributors.training_frequency.map(|v| v.into()),
+            training_volume_contribution: contributors.training_volume.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl From<&ActivityClassType> for i64 {
+    fn from(value: &ActivityClassType) -> i64 {
+        match value {
+            ActivityClassType::NonWear => 0,
+            ActivityClassType::Rest => 1,
+            ActivityClassType::Inactive => 2,
+            ActivityClassType::LowActivity => 3,
+            ActivityClassType::MediumActivity => 4,
+            ActivityClassType::HighActivity => 5,
+        }
+    }
+}
+
+impl TryFrom<&ActivityClass> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &ActivityClass) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::ActivityClass(ActivityClassDataPoint {
+            class: (&value.activity_class).into(),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Met> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Met) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Met(MetDataPoint {
+            met: value.met.into(),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&DailySleepScore> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &DailySleepScore,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        let contributors = &value.contributors;
+
+        Ok(InfluxDBMeasurement::DailySleep(DailySleepDataPoint {
+            id: value.id.to_string(),
+            score: value.score.map(|v| v.into()),
+            deep_sleep_contribution: contributors.deep_sleep.map(|v| v.into()),
+            efficiency_contribution: contributors.efficiency.map(|v| v.into()),
+            latency_contribution: contributors.latency.map(|v| v.into()),
+            rem_sleep_contribution: contributors.rem_sleep.map(|v| v.into()),
+            restfulness_contribution: contributors.restfulness.map(|v| v.into()),
+            timing_contribution: contributors.timing.map(|v| v.into()),
+            total_sleep_contribution: contributors.total_sleep.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Workout> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Workout) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Workout(WorkoutDataPoint {
+            id: value.id.to_string(),
+            activity: value.activity.to_string(),
+            intensity: value.intensity.to_string(),
+            source: value.source.to_string(),
+            duration: value.duration_in_seconds(),
+            calories: value.calories.map(|v| v.into()),
+            distance: value.distance.map(|v| v.into()),
+            label: value.label.clone(),
+            end_datetime: value.end_datetime.timestamp(),
+            start_datetime: value.start_datetime.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Session> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Session) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Session(SessionDataPoint {
+            id: value.id.to_string(),
+            session_type: value.session_type.to_string(),
+            mood: value.mood.clone(),
+            duration: value.duration_in_seconds(),
+            end_datetime: value.end_datetime.timestamp(),
+            start_datetime: value.start_datetime.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&SessionSample> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &SessionSample) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        let sample_value = value.value.round() as i64;
+        let timestamp = value.timestamp.timestamp();
+        let session_id = value.session_id.to_string();
+        let session_type = value.session_type.to_string();
+        let person_name = value.person_name.to_string();
+
+        Ok(match value.sample_type {
+            SessionSampleType::HeartRate => {
+                InfluxDBMeasurement::SessionHeartRate(SessionHeartRateDataPoint {
+                    bpm: sample_value,
+                    timestamp,
+                    session_id,
+                    session_type,
+                    person_name,
+                })
+            }
+            SessionSampleType::HeartRateVariability => {
+                InfluxDBMeasurement::SessionHeartRateVariability(
+                    SessionHeartRateVariabilityDataPoint {
+                        ms: sample_value,
+                        timestamp,
+                        session_id,
+                        session_type,
+                        person_name,
+                    },
+                )
+            }
+            SessionSampleType::MotionCount => {
+                InfluxDBMeasurement::SessionMotionCount(SessionMotionCountDataPoint {
+                    motion_count: sample_value,
+                    timestamp,
+                    session_id,
+                    session_type,
+                    person_name,
+                })
+            }
+        })
+    }
+}
+
+impl TryFrom<&SpO2> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &SpO2) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::SpO2(SpO2DataPoint {
+            id: value.id.to_string(),
+            average_spo2_percentage: value.average_spo2_percentage.map(|v| v.into()),
+            breathing_disturbance_index: value.breathing_disturbance_index.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Stress> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Stress) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        let day_summary = match &value.day_summary {
+            Some(day_summary) => day_summary.to_string(),
+            None => String::from("unknown"),
+        };
+
+        Ok(InfluxDBMeasurement::Stress(StressDataPoint {
+            id: value.id.to_string(),
+            day_summary,
+            stress_high: value.stress_high.map(|v| v.into()),
+            recovery_high: value.recovery_high.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Resilience> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Resilience) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Resilience(ResilienceDataPoint {
+            id: value.id.to_string(),
+            level: value.level.to_string(),
+            sleep_recovery_contribution: value.contributors.sleep_recovery.into(),
+            daytime_recovery_contribution: value.contributors.daytime_recovery.into(),
+            stress_contribution: value.contributors.stress.into(),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&CardiovascularAge> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &CardiovascularAge,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::CardiovascularAge(
+            CardiovascularAgeDataPoint {
+                vascular_age: value.vascular_age.map(|v| v.into()),
+                timestamp: value.timestamp.timestamp(),
+                person_name: value.person_name.to_string(),
+            },
+        ))
+    }
+}
+
+impl TryFrom<&VO2Max> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &VO2Max) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::VO2Max(VO2MaxDataPoint {
+            id: value.id.to_string(),
+            vo2_max: value.vo2_max.map(|v| v.into()),
+            timestamp: value.timestamp.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&Tag> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Tag) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Tag(TagDataPoint {
+            id: value.id.to_string(),
+            tag_type: value.tag_type.to_string(),
+            source: value.source.to_string(),
+            comment: value.comment.clone(),
+            duration: value.duration_in_seconds(),
+            end_datetime: value
+                .end_datetime
+                .unwrap_or(value.start_datetime)
+                .timestamp(),
+            start_datetime: value.start_datetime.timestamp(),
+            person_name: value.person_name.to_string(),
+        }))
+    }
+}
+
+impl TryFrom<&RestModePeriod> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &RestModePeriod) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::RestModePeriod(
+            RestModePeriodDataPoint {
+                id: value.id.to_string(),
+                ongoing: value.is_ongoing(),
+                duration: value.duration_in_seconds(),
+                start_day: value.start_day.timestamp(),
+                end_day: value.end_day.map(|end_day| end_day.timestamp()),
+                end_datetime: value
+                    .end_datetime
+                    .map(|end_datetime| end_datetime.timestamp()),
+                episode_count: value.episodes.len() as i64,
+                start_datetime: value.start_datetime.timestamp(),
+                person_name: value.person_name.to_string(),
+            },
+        ))
+    }
+}
+
+impl TryFrom<&BedtimeRecommendation> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(
+        value: &BedtimeRecommendation,
+    ) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        let recommendation = match &value.recommendation {
+            Some(recommendation) => recommendation.to_string(),
+            None => String::from("unknown"),
+        };
+        let status = match &value.status {
+            Some(status) => status.to_string(),
+            None => String::from("unknown"),
+        };
+        let optimal_bedtime = value.optimal_bedtime.as_ref();
+
+        Ok(InfluxDBMeasurement::BedtimeRecommendation(
+            BedtimeRecommendationDataPoint {
+                id: value.id.to_string(),
+                recommendation,
+                status,
+                has_optimal_bedtime: optimal_bedtime.is_some(),
+                optimal_bedtime_start: optimal_bedtime.map(|v| v.start.timestamp()),
+                optimal_bedtime_end: optimal_bedtime.map(|v| v.end.timestamp()),
+                optimal_bedtime_start_offset: optimal_bedtime.map(|v| v.start_offset.into()),
+                optimal_bedtime_end_offset: optimal_bedtime.map(|v| v.end_offset.into()),
+                timestamp: value.timestamp.timestamp(),
+                person_name: value.person_name.to_string(),
+            },
+        ))
+    }
+}
+
+impl TryFrom<&Baseline> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &Baseline) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::Baseline(BaselineDataPoint {
+            metric: value.metric.to_string(),
+            window: format!("{}d", value.window_in_days),
+            value: value.value.into(),
+            mean: value.mean.into(),
+            standard_deviation: value.standard_deviation.into(),
+            z_score: value.z_score.map(f64::from),
+            samples: value.samples.into(),
             timestamp: value.timestamp.timestamp(),
             person_name: value.person_name.to_string(),
         }))
     }
 }
+
+impl TryFrom<&PersonMetadata> for InfluxDBMeasurement {
+    type Error = MeasurementConvertingError;
+
+    fn try_from(value: &PersonMetadata) -> Result<InfluxDBMeasurement, MeasurementConvertingError> {
+        Ok(InfluxDBMeasurement::PersonMetadata(
+            PersonMetadataDataPoint {
+                age: value.age.map(|v| v.into()),
+                weight: value.weight.map(|v| v.into()),
+                height: value.height.map(|v| v.into()),
+                timestamp: value.timestamp.timestamp(),
+                person_name: value.person_name.to_string(),
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InfluxDBMeasurement, TaggedMeasurement};
+    use crate::pollers::{Met, PersonMetadata};
+    use chrono::{DateTime, Utc};
+    use influxdb2::models::WriteDataPoint;
+
+    fn met_measurement() -> InfluxDBMeasurement {
+        let met = Met {
+            met: 1.5,
+            timestamp: "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap(),
+            person_name: "test_person".to_owned(),
+        };
+
+        (&met).try_into().unwrap()
+    }
+
+    fn write_to_string(data_point: impl WriteDataPoint) -> String {
+        let mut line: Vec<u8> = Vec::new();
+        data_point.write_data_point_to(&mut line).unwrap();
+
+        String::from_utf8(line).unwrap()
+    }
+
+    #[test]
+    fn test_tagged_measurement_appends_extra_tags() {
+        let tagged_measurement = TaggedMeasurement {
+            measurement: met_measurement(),
+            extra_tags: vec![
+                ("ring_firmware_version", "2.9.15".to_owned()),
+                ("ring_color", "brushed silver".to_owned()),
+            ],
+        };
+
+        assert_eq!(
+            write_to_string(tagged_measurement),
+            "met,person_name=test_person,ring_firmware_version=2.9.15,ring_color=brushed\\ silver met=1.5 1609459200\n"
+        );
+    }
+
+    #[test]
+    fn test_tagged_measurement_escapes_extra_tags() {
+        let tagged_measurement = TaggedMeasurement {
+            measurement: met_measurement(),
+            extra_tags: vec![
+                ("ring_color", "silver\\".to_owned()),
+                ("ring_hardware_type", "gen=3,a".to_owned()),
+            ],
+        };
+
+        assert_eq!(
+            write_to_string(tagged_measurement),
+            "met,person_name=test_person,ring_color=silver\\\\,ring_hardware_type=gen\\=3\\,a met=1.5 1609459200\n"
+        );
+    }
+
+    #[test]
+    fn test_person_metadata_measurement() {
+        let person_metadata = PersonMetadata {
+            age: Some(34),
+            weight: Some(72.5),
+            height: Some(168.0),
+            ring_firmware_version: Some("2.9.15".to_owned()),
+            timestamp: "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap(),
+            person_name: "test_person".to_owned(),
+            ..Default::default()
+        };
+        let measurement: InfluxDBMeasurement = (&person_metadata).try_into().unwrap();
+
+        assert_eq!(
+            write_to_string(measurement),
+            "person_metadata,person_name=test_person age=34i,weight=72.5,height=168 1609459200\n"
+        );
+    }
+
+    #[test]
+    fn test_tagged_measurement_without_extra_tags() {
+        let tagged_measurement = TaggedMeasurement {
+            measurement: met_measurement(),
+            extra_tags: vec![],
+        };
+
+        assert_eq!(
+            write_to_string(tagged_measurement),
+            write_to_string(met_measurement())
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::pollers::PersonMetadata;
+use std::collections::HashMap;
+
+/// Keeps track of the latest known metadata of each person so that it can be attached as extra
+/// tags to the measurements of that person.
+#[derive(Debug, Default)]
+pub struct MetadataTags {
+    tags_by_person: HashMap<String, Vec<(&'static str, String)>>,
+}
+
+impl MetadataTags {
+    pub fn update(&mut self, person_metadata: &PersonMetadata) {
+        self.tags_by_person.insert(
+            person_metadata.person_name.to_owned(),
+            person_metadata.to_tags(),
+        );
+    }
+
+    pub fn get(&self, person_name: Option<&str>) -> Vec<(&'static str, String)> {
+        person_name
+            .and_then(|person_name| self.tags_by_person.get(person_name))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
@@ -1,5 +1,6 @@
 mod export_item;
 mod influx_db_measurement;
+mod metadata_tags;
 
 use crate::pollers::OuraData;
 use export_item::ExportItem;
@@ -8,19 +9,38 @@ use influxdb2::{api::write::TimestampPrecision, Client};
 use itertools::{Either, Itertools};
 use log::{debug, error};
 
-use self::influx_db_measurement::InfluxDBMeasurement;
+use self::influx_db_measurement::TaggedMeasurement;
+pub use metadata_tags::MetadataTags;
 
 pub async fn export_oura_data(
     oura_data_stream: impl Stream<Item = OuraData>,
     influxdb_env: &Option<(Client, String)>,
+    metadata_tags: &mut MetadataTags,
 ) {
     oura_data_stream
         .flat_map(|oura_data| {
             let data: &OuraData = &oura_data;
+
+            if let OuraData::PersonMetadata(person_metadata) = data {
+                metadata_tags.update(person_metadata);
+            }
+
+            let extra_tags = metadata_tags.get(data.get_person_name());
             let export_items: Result<Vec<ExportItem>, _> = data.try_into();
 
             match export_items {
-                Ok(export_items) => stream::iter(export_items),
+                Ok(export_items) => stream::iter(
+                    export_items
+                        .into_iter()
+                        .map(|export_item| match export_item {
+                            ExportItem::MQTT(message) => Either::Right(message),
+                            ExportItem::InfluxDB(measurement) => Either::Left(TaggedMeasurement {
+                                measurement,
+                                extra_tags: extra_tags.clone(),
+                            }),
+                        })
+                        .collect::<Vec<_>>(),
+                ),
                 Err(err) => {
                     error!("Error generating export items: {}", err);
                     stream::iter(vec![])
@@ -31,10 +51,7 @@ pub async fn export_oura_data(
         .for_each_concurrent(None, |export_items| async move {
             let (influxdb_data_points, _mqtt_export_items): (Vec<_>, Vec<_>) = export_items
                 .into_iter()
-                .partition_map(|export_item| match export_item {
-                    ExportItem::MQTT(message) => Either::Right(message),
-                    ExportItem::InfluxDB(data_point) => Either::Left(data_point),
-                });
+                .partition_map(|export_item| export_item);
 
             if let Some((client, bucket)) = influxdb_env {
                 write_to_influxdb(influxdb_data_points, client, bucket).await
@@ -44,9 +61,9 @@ pub async fn export_oura_data(
 }
 
 async fn write_to_influxdb(
-    influxdb_data_points: Vec<InfluxDBMeasurement>,
+    influxdb_data_points: Vec<TaggedMeasurement>,
     client: &Client,
-    bucket: &String,
+    bucket: &str,
 ) {
     debug!("Writing to InfluxDB: {:?}", influxdb_data_points);
 
@@ -3,10 +3,12 @@ use futures::{stream, StreamExt};
 use influxdb2::Client;
 use log::{error, info};
 
+mod baselines;
 mod config;
 mod exporters;
 mod oura_api;
 mod pollers;
+mod webhooks;
 
 use crate::config::Config;
 use config::InfluxDB;
@@ -14,15 +16,11 @@ use config::InfluxDB;
 fn initialize_config_and_logging() -> Config {
     let mut logger_builder = env_logger::Builder::from_env("LOG_LEVEL");
 
-    return match config::get_config() {
+    match config::get_config() {
         Ok(config) => {
-            match &config.log_level {
-                Some(level) => {
-                    logger_builder.filter_level(level.into());
-                    ()
-                }
-                None => (),
-            };
+            if let Some(level) = &config.log_level {
+                logger_builder.filter_level(level.into());
+            }
 
             logger_builder.init();
             config
@@ -32,16 +30,17 @@ fn initialize_config_and_logging() -> Config {
             error!("Error reading configuration: {}", e);
             std::process::exit(exitcode::CONFIG);
         }
-    };
+    }
 }
 
 async fn poll(
     poll_interval: u16,
-    persons: &Vec<config::OuraPerson>,
+    persons: &[config::OuraPerson],
     oura_api_config: &Option<config::OuraApi>,
+    poll_person_metadata: bool,
     tx: tokio::sync::mpsc::UnboundedSender<Vec<pollers::OuraData>>,
 ) {
-    match pollers::Poller::initialize_with_persons(persons, oura_api_config) {
+    match pollers::Poller::initialize_with_persons(persons, oura_api_config, poll_person_metadata) {
         Ok(poller) => {
             let seconds_in_past: i64 = poll_interval.into();
             let sleep_time: u64 = poll_interval.into();
@@ -49,7 +48,7 @@ async fn poll(
                 Utc::now() - Duration::seconds(seconds_in_past) - Duration::hours(48);
 
             loop {
-                let start_time = latest_timestamp.clone();
+                let start_time = latest_timestamp;
                 let end_time = Utc::now();
                 let mut chunk_stream = poller.poll_oura_data(&start_time, &end_time).chunks(100);
 
@@ -88,8 +87,94 @@ async fn poll(
     }
 }
 
-fn get_influxdb_env<'a>(config: Option<InfluxDB>) -> Option<(Client, String)> {
-    return match config {
+async fn receive_webhooks(
+    webhook_config: &config::Webhook,
+    persons: &[config::OuraPerson],
+    oura_api_config: &Option<config::OuraApi>,
+    poll_person_metadata: bool,
+    tx: tokio::sync::mpsc::UnboundedSender<Vec<pollers::OuraData>>,
+) {
+    let poller = match pollers::Poller::initialize_with_persons(
+        persons,
+        oura_api_config,
+        poll_person_metadata,
+    ) {
+        Ok(poller) => poller,
+        Err(e) => {
+            error!("Error initializing poller: {}", e);
+            std::process::exit(exitcode::CONFIG);
+        }
+    };
+
+    let webhook_client = match oura_api::OuraWebhookClient::from_config(
+        oura_api_config,
+        &webhook_config.client_id,
+        &webhook_config.client_secret,
+    ) {
+        Ok(webhook_client) => webhook_client,
+        Err(e) => {
+            error!("Error initializing webhook client: {}", e);
+            std::process::exit(exitcode::CONFIG);
+        }
+    };
+
+    let listen_address = webhook_config
+        .listen_address
+        .as_deref()
+        .unwrap_or(webhooks::DEFAULT_LISTEN_ADDRESS);
+    let listener = match tokio::net::TcpListener::bind(listen_address).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Error listening on {}: {}", listen_address, e);
+            std::process::exit(exitcode::OSERR);
+        }
+    };
+    info!("Receiving webhook notifications on {}", listen_address);
+
+    if let Err(e) =
+        webhooks::receive_webhooks(listener, webhook_config, &webhook_client, &poller, tx).await
+    {
+        error!("Error receiving webhook notifications: {}", e);
+        std::process::exit(exitcode::CONFIG);
+    }
+}
+
+async fn authorize(
+    persons: &[config::OuraPerson],
+    oura_api_config: &Option<config::OuraApi>,
+    person_name: Option<&String>,
+) {
+    let person = match person_name {
+        Some(person_name) => persons.iter().find(|person| &person.name == person_name),
+        None => None,
+    };
+
+    let person = match person {
+        Some(person) => person,
+        None => {
+            error!(
+                "Usage: authorize <person name>, where the person is one of the configured persons"
+            );
+            std::process::exit(exitcode::USAGE);
+        }
+    };
+
+    let result = match oura_api::OuraHttpClient::from_config(oura_api_config, person) {
+        Ok(client) => client.authorize().await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(_) => info!("Authorization of '{}' completed", person.name),
+        Err(e) => {
+            error!("Error authorizing '{}': {}", person.name, e);
+            std::process::exit(exitcode::SOFTWARE);
+        }
+    }
+}
+
+fn get_influxdb_env(config: Option<InfluxDB>) -> Option<(Client, String)> {
+    match config {
         Some(influxdb_config) => {
             let client = Client::new(
                 &influxdb_config.url,
@@ -99,7 +184,7 @@ fn get_influxdb_env<'a>(config: Option<InfluxDB>) -> Option<(Client, String)> {
             Some((client, influxdb_config.bucket))
         }
         None => None,
-    };
+    }
 }
 
 #[tokio::main]
@@ -111,16 +196,65 @@ async fn main() {
         persons,
         log_level: _,
         oura_api,
+        webhook,
+        baselines: baselines_config,
     } = config;
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.get(1).map(String::as_str) == Some("authorize") {
+        authorize(&persons, &oura_api, args.get(2)).await;
+        return;
+    }
+
+    let metadata_tags_enabled = match &influxdb {
+        Some(influxdb_config) => influxdb_config.metadata_tags.unwrap_or(false),
+        None => false,
+    };
     let influxdb_env = get_influxdb_env(influxdb);
+    let mut metadata_tags = exporters::MetadataTags::default();
+    let mut baseline_tracker = match &baselines_config {
+        Some(config) => match baselines::BaselineTracker::from_config(config) {
+            Ok(baseline_tracker) => Some(baseline_tracker),
+            Err(e) => {
+                error!("Error initializing baselines: {}", e);
+                std::process::exit(exitcode::CONFIG);
+            }
+        },
+        None => None,
+    };
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
 
     tokio::spawn(async move {
-        poll(poller_interval, &persons, &oura_api, tx).await;
+        match webhook {
+            Some(webhook_config) => {
+                receive_webhooks(
+                    &webhook_config,
+                    &persons,
+                    &oura_api,
+                    metadata_tags_enabled,
+                    tx,
+                )
+                .await;
+            }
+            None => {
+                poll(
+                    poller_interval,
+                    &persons,
+                    &oura_api,
+                    metadata_tags_enabled,
+                    tx,
+                )
+                .await;
+            }
+        }
     });
 
     while let Some(data) = rx.recv().await {
-        exporters::export_oura_data(stream::iter(data), &influxdb_env).await
+        let data = match &mut baseline_tracker {
+            Some(baseline_tracker) => baseline_tracker.track(data).await,
+            None => data,
+        };
+        exporters::export_oura_data(stream::iter(data), &influxdb_env, &mut metadata_tags).await
     }
 }
 
@@ -0,0 +1,42 @@
+use super::sleep::OuraSleepMeasurement;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraActivityDocument {
+    pub id: String,
+    pub active_calories: i32,
+    pub average_met_minutes: f32,
+    pub class_5_min: Option<String>,
+    pub contributors: OuraActivityContributors,
+    pub day: String,
+    pub equivalent_walking_distance: i32,
+    pub high_activity_met_minutes: i32,
+    pub high_activity_time: i32,
+    pub inactivity_alerts: i32,
+    pub low_activity_met_minutes: i32,
+    pub low_activity_time: i32,
+    pub medium_activity_met_minutes: i32,
+    pub medium_activity_time: i32,
+    pub met: OuraSleepMeasurement,
+    pub meters_to_target: i32,
+    pub non_wear_time: i32,
+    pub resting_time: i32,
+    pub score: Option<u8>,
+    pub sedentary_met_minutes: i32,
+    pub sedentary_time: i32,
+    pub steps: i32,
+    pub target_calories: i32,
+    pub target_meters: i32,
+    pub timestamp: String,
+    pub total_calories: i32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraActivityContributors {
+    pub meet_daily_targets: Option<u8>,
+    pub move_every_hour: Option<u8>,
+    pub recovery_time: Option<u8>,
+    pub stay_active: Option<u8>,
+    pub training_frequency: Option<u8>,
+    pub training_volume: Option<u8>,
+}
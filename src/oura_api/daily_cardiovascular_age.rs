@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailyCardiovascularAgeDocument {
+    pub day: String,
+    pub vascular_age: Option<i32>,
+}
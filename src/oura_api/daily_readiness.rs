@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailyReadinessDocument {
+    pub id: String,
+    pub contributors: OuraDailyReadinessContributors,
+    pub day: String,
+    pub score: Option<u8>,
+    pub temperature_deviation: Option<f32>,
+    pub temperature_trend_deviation: Option<f32>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailyReadinessContributors {
+    pub activity_balance: Option<u8>,
+    pub body_temperature: Option<u8>,
+    pub hrv_balance: Option<u8>,
+    pub previous_day_activity: Option<u8>,
+    pub previous_night: Option<u8>,
+    pub recovery_index: Option<u8>,
+    pub resting_heart_rate: Option<u8>,
+    pub sleep_balance: Option<u8>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailyResilienceDocument {
+    pub id: String,
+    pub contributors: OuraResilienceContributors,
+    pub day: String,
+    pub level: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraResilienceContributors {
+    pub sleep_recovery: f32,
+    pub daytime_recovery: f32,
+    pub stress: f32,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailySleepDocument {
+    pub id: String,
+    pub contributors: OuraDailySleepContributors,
+    pub day: String,
+    pub score: Option<u8>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailySleepContributors {
+    pub deep_sleep: Option<u8>,
+    pub efficiency: Option<u8>,
+    pub latency: Option<u8>,
+    pub rem_sleep: Option<u8>,
+    pub restfulness: Option<u8>,
+    pub timing: Option<u8>,
+    pub total_sleep: Option<u8>,
+}
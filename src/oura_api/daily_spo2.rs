@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailySpO2Document {
+    pub id: String,
+    pub breathing_disturbance_index: Option<i32>,
+    pub day: String,
+    pub spo2_percentage: Option<OuraSpO2Percentage>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraSpO2Percentage {
+    pub average: Option<f32>,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraDailyStressDocument {
+    pub id: String,
+    pub day: String,
+    pub day_summary: Option<String>,
+    pub recovery_high: Option<i32>,
+    pub stress_high: Option<i32>,
+}
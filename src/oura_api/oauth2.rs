@@ -0,0 +1,410 @@
+use super::{map_response_into_response_error, OuraApiError};
+use crate::config::{OuraOAuth2, OuraPerson};
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, info};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+pub const OAUTH2_TOKEN_PATH: &str = "oauth/token";
+
+const OAUTH2_AUTHORIZATION_URL: &str = "https://cloud.ouraring.com/oauth/authorize";
+const DEFAULT_REDIRECT_URI: &str = "http://localhost:8085/callback";
+const OAUTH2_SCOPES: &str = "email personal daily heartrate workout tag session spo2Daily";
+
+/// Access tokens are refreshed slightly before they expire so that they do not expire in flight.
+const ACCESS_TOKEN_EXPIRY_MARGIN_IN_SECONDS: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OAuth2Tokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OAuth2TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: String,
+}
+
+impl OAuth2TokenResponse {
+    fn into_tokens(self) -> OAuth2Tokens {
+        OAuth2Tokens {
+            access_token: self.access_token,
+            refresh_token: self.refresh_token,
+            expires_at: Utc::now() + Duration::seconds(self.expires_in),
+        }
+    }
+}
+
+/// Holds the OAuth2 tokens of a person. The tokens are persisted to a state file whenever they are
+/// refreshed, because Oura rotates the refresh token on every refresh.
+pub struct OAuth2Session<'a> {
+    config: &'a OuraOAuth2,
+    state_file: String,
+    tokens: Mutex<Option<OAuth2Tokens>>,
+}
+
+impl<'a> OAuth2Session<'a> {
+    pub fn from_person(
+        person: &'a OuraPerson,
+        config: &'a OuraOAuth2,
+    ) -> Result<OAuth2Session<'a>, OuraApiError> {
+        let state_file = match &config.state_file {
+            Some(state_file) => state_file.to_string(),
+            None => format!(
+                "{}.oauth2.json",
+                person.name.to_lowercase().replace(' ', "_")
+            ),
+        };
+        let tokens = load_tokens(&state_file)?;
+
+        Ok(OAuth2Session {
+            config,
+            state_file,
+            tokens: Mutex::new(tokens),
+        })
+    }
+
+    pub async fn access_token(
+        &self,
+        client: &reqwest::Client,
+        token_url: &str,
+    ) -> Result<String, OuraApiError> {
+        let mut tokens = self.tokens.lock().await;
+        let refresh_at = Utc::now() + Duration::seconds(ACCESS_TOKEN_EXPIRY_MARGIN_IN_SECONDS);
+
+        match tokens.as_ref() {
+            Some(current) if current.expires_at > refresh_at => Ok(current.access_token.clone()),
+            _ => self.refresh(&mut tokens, client, token_url).await,
+        }
+    }
+
+    pub async fn refresh_rejected_access_token(
+        &self,
+        client: &reqwest::Client,
+        token_url: &str,
+        rejected_access_token: &str,
+    ) -> Result<String, OuraApiError> {
+        let mut tokens = self.tokens.lock().await;
+
+        match tokens.as_ref() {
+            // Another request already refreshed the token while this one was waiting for the lock.
+            Some(current) if current.access_token != rejected_access_token => {
+                Ok(current.access_token.clone())
+            }
+            _ => self.refresh(&mut tokens, client, token_url).await,
+        }
+    }
+
+    async fn refresh(
+        &self,
+        tokens: &mut Option<OAuth2Tokens>,
+        client: &reqwest::Client,
+        token_url: &str,
+    ) -> Result<String, OuraApiError> {
+        let refresh_token = match (tokens.as_ref(), &self.config.refresh_token) {
+            (Some(current), _) => current.refresh_token.clone(),
+            (None, Some(refresh_token)) => refresh_token.to_string(),
+            (None, None) => {
+                return Err(OuraApiError::OAuth2Error(format!(
+                    "No refresh token found in '{}' or in the configuration, run the authorize command first",
+                    self.state_file
+                )))
+            }
+        };
+
+        info!("Refreshing OAuth2 access token");
+        let refreshed_tokens = request_tokens(
+            client,
+            token_url,
+            &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", &refresh_token),
+                ("client_id", &self.config.client_id),
+                ("client_secret", &self.config.client_secret),
+            ],
+        )
+        .await?;
+
+        save_tokens(&self.state_file, &refreshed_tokens)?;
+        let access_token = refreshed_tokens.access_token.clone();
+        *tokens = Some(refreshed_tokens);
+
+        Ok(access_token)
+    }
+
+    /// Runs the authorization code flow: the user opens the printed authorization URL in a
+    /// browser, and the authorization code is received through a loopback redirect listener and
+    /// exchanged for the initial tokens.
+    pub async fn authorize(
+        &self,
+        client: &reqwest::Client,
+        token_url: &str,
+    ) -> Result<(), OuraApiError> {
+        let redirect_uri = self
+            .config
+            .redirect_uri
+            .as_deref()
+            .unwrap_or(DEFAULT_REDIRECT_URI);
+        let parsed_redirect_uri = Url::parse(redirect_uri).map_err(|err| {
+            OuraApiError::OAuth2Error(format!("Invalid redirect URI '{}': {}", redirect_uri, err))
+        })?;
+        let state = random_state()?;
+        let authorization_url = Url::parse_with_params(
+            OAUTH2_AUTHORIZATION_URL,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.config.client_id),
+                ("redirect_uri", redirect_uri),
+                ("scope", OAUTH2_SCOPES),
+                ("state", &state),
+            ],
+        )
+        .map_err(|err| OuraApiError::OAuth2Error(err.to_string()))?;
+
+        println!(
+            "Open the following URL in a browser to authorize the exporter:\n\n{}\n",
+            authorization_url
+        );
+
+        let code = receive_authorization_code(&parsed_redirect_uri, &state).await?;
+        let tokens = request_tokens(
+            client,
+            token_url,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", &code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", &self.config.client_id),
+                ("client_secret", &self.config.client_secret),
+            ],
+        )
+        .await?;
+
+        save_tokens(&self.state_file, &tokens)?;
+        *self.tokens.lock().await = Some(tokens);
+        info!("OAuth2 tokens saved to '{}'", self.state_file);
+
+        Ok(())
+    }
+}
+
+fn load_tokens(state_file: &str) -> Result<Option<OAuth2Tokens>, OuraApiError> {
+    match fs::read_to_string(state_file) {
+        Ok(content) => serde_json::from_str(&content).map(Some).map_err(|err| {
+            OuraApiError::OAuth2Error(format!(
+                "Cannot parse OAuth2 state file '{}': {}",
+                state_file, err
+            ))
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(OuraApiError::OAuth2Error(format!(
+            "Cannot read OAuth2 state file '{}': {}",
+            state_file, err
+        ))),
+    }
+}
+
+fn save_tokens(state_file: &str, tokens: &OAuth2Tokens) -> Result<(), OuraApiError> {
+    let content = serde_json::to_string_pretty(tokens)
+        .map_err(|err| OuraApiError::OAuth2Error(err.to_string()))?;
+
+    // Write to a temporary file first so that a crash cannot leave a truncated state file behind
+    // and lose the rotated refresh token.
+    let temporary_file = format!("{}.tmp", state_file);
+    fs::write(&temporary_file, content)
+        .and_then(|_| fs::rename(&temporary_file, state_file))
+        .map_err(|err| {
+            OuraApiError::OAuth2Error(format!(
+                "Cannot write OAuth2 state file '{}': {}",
+                state_file, err
+            ))
+        })
+}
+
+async fn request_tokens(
+    client: &reqwest::Client,
+    token_url: &str,
+    form: &[(&str, &str)],
+) -> Result<OAuth2Tokens, OuraApiError> {
+    debug!("Requesting OAuth2 tokens: url={}", token_url);
+
+    let response = client
+        .post(token_url)
+        .form(form)
+        .send()
+        .await
+        .map_err(OuraApiError::RequestError)?;
+
+    if !response.status().is_success() {
+        return Err(map_response_into_response_error(response).await);
+    }
+
+    let token_response = response
+        .json::<OAuth2TokenResponse>()
+        .await
+        .map_err(OuraApiError::RequestError)?;
+
+    Ok(token_response.into_tokens())
+}
+
+/// The `state` parameter protects the callback against cross-site request forgery, so it comes
+/// from the random number generator of the operating system.
+fn random_state() -> Result<String, OuraApiError> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|err| {
+        OuraApiError::OAuth2Error(format!("Cannot generate OAuth2 state: {}", err))
+    })?;
+
+    Ok(hex::encode(bytes))
+}
+
+/// Returns the query parameters of a request to the callback path, or `None` for requests to any
+/// other path, such as the favicon requests made by browsers.
+fn parse_callback_request(request: &str, callback_path: &str) -> Option<Vec<(String, String)>> {
+    let target = request.lines().next()?.split(' ').nth(1)?;
+    let url = Url::parse(&format!("http://localhost{}", target)).ok()?;
+
+    if url.path() != callback_path {
+        return None;
+    }
+
+    Some(url.query_pairs().into_owned().collect())
+}
+
+fn find_parameter<'b>(parameters: &'b [(String, String)], name: &str) -> Option<&'b str> {
+    parameters
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn callback_result(
+    parameters: &[(String, String)],
+    expected_state: &str,
+) -> Result<String, String> {
+    if let Some(error) = find_parameter(parameters, "error") {
+        return Err(format!("Authorization was not granted: {}", error));
+    }
+
+    if find_parameter(parameters, "state") != Some(expected_state) {
+        return Err(String::from("Authorization state does not match"));
+    }
+
+    find_parameter(parameters, "code")
+        .map(String::from)
+        .ok_or_else(|| String::from("Authorization code is missing"))
+}
+
+async fn receive_authorization_code(
+    redirect_uri: &Url,
+    expected_state: &str,
+) -> Result<String, OuraApiError> {
+    let port = redirect_uri.port_or_known_default().unwrap_or(80);
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|err| {
+            OuraApiError::OAuth2Error(format!("Cannot listen on port {}: {}", port, err))
+        })?;
+    info!("Waiting for the authorization redirect on port {}", port);
+
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|err| OuraApiError::OAuth2Error(err.to_string()))?;
+        let mut buffer = vec![0; 8192];
+        let read = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|err| OuraApiError::OAuth2Error(err.to_string()))?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+
+        let (status, body, result) = match parse_callback_request(&request, redirect_uri.path()) {
+            None => ("404 Not Found", String::from("Not found"), None),
+            Some(parameters) => match callback_result(&parameters, expected_state) {
+                Ok(code) => (
+                    "200 OK",
+                    String::from("Authorization completed, you can close this window."),
+                    Some(Ok(code)),
+                ),
+                Err(error) => ("400 Bad Request", error.clone(), Some(Err(error))),
+            },
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream
+            .write_all(response.as_bytes())
+            .await
+            .map_err(|err| OuraApiError::OAuth2Error(err.to_string()))?;
+
+        if let Some(result) = result {
+            return result.map_err(OuraApiError::OAuth2Error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{callback_result, parse_callback_request, random_state};
+
+    #[test]
+    fn test_random_state() {
+        let state = random_state().unwrap();
+
+        assert_eq!(state.len(), 32);
+        assert!(state.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(state, random_state().unwrap());
+    }
+
+    #[test]
+    fn test_parse_callback_request() {
+        let request = "GET /callback?code=abc&state=xyz HTTP/1.1\r\nHost: localhost:8085\r\n\r\n";
+
+        let parameters = parse_callback_request(request, "/callback").unwrap();
+
+        assert_eq!(
+            parameters,
+            vec![
+                ("code".to_owned(), "abc".to_owned()),
+                ("state".to_owned(), "xyz".to_owned())
+            ]
+        );
+        assert_eq!(callback_result(&parameters, "xyz"), Ok("abc".to_owned()));
+        assert_eq!(
+            callback_result(&parameters, "other"),
+            Err("Authorization state does not match".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_parse_callback_request_ignores_other_paths() {
+        let request = "GET /favicon.ico HTTP/1.1\r\nHost: localhost:8085\r\n\r\n";
+
+        assert_eq!(parse_callback_request(request, "/callback"), None);
+    }
+
+    #[test]
+    fn test_callback_result_with_denied_access() {
+        let parameters = vec![
+            ("error".to_owned(), "access_denied".to_owned()),
+            ("state".to_owned(), "xyz".to_owned()),
+        ];
+
+        assert_eq!(
+            callback_result(&parameters, "xyz"),
+            Err("Authorization was not granted: access_denied".to_owned())
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraPersonalInfoDocument {
+    pub id: String,
+    pub age: Option<i32>,
+    pub weight: Option<f32>,
+    pub height: Option<f32>,
+    pub biological_sex: Option<String>,
+    pub email: Option<String>,
+}
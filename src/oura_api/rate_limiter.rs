@@ -0,0 +1,101 @@
+use log::debug;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Oura allows 5000 requests per 5 minutes for each access token.
+pub const OURA_API_REQUESTS_PER_WINDOW: u32 = 5000;
+pub const OURA_API_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// A token bucket that holds at most `capacity` tokens and refills them evenly over `window`.
+/// Every request takes one token, and when the bucket is empty the request waits until a token
+/// has been refilled.
+pub struct RateLimiter {
+    capacity: f64,
+    tokens_per_second: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, window: Duration) -> RateLimiter {
+        let capacity: f64 = capacity.into();
+
+        RateLimiter {
+            capacity,
+            tokens_per_second: capacity / window.as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().await;
+                let now = Instant::now();
+                let refilled_tokens =
+                    (now - bucket.refilled_at).as_secs_f64() * self.tokens_per_second;
+
+                bucket.tokens = (bucket.tokens + refilled_tokens).min(self.capacity);
+                bucket.refilled_at = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.tokens_per_second)
+            };
+
+            debug!(
+                "Request budget for Oura API used up, waiting {} ms",
+                wait.as_millis()
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> RateLimiter {
+        RateLimiter::new(OURA_API_REQUESTS_PER_WINDOW, OURA_API_RATE_LIMIT_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RateLimiter;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn test_acquire_within_capacity_does_not_wait() {
+        let rate_limiter = RateLimiter::new(3, Duration::from_secs(60));
+        let started_at = Instant::now();
+
+        for _ in 0..3 {
+            rate_limiter.acquire().await;
+        }
+
+        assert!(started_at.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill_when_empty() {
+        let rate_limiter = RateLimiter::new(2, Duration::from_millis(200));
+        let started_at = Instant::now();
+
+        for _ in 0..3 {
+            rate_limiter.acquire().await;
+        }
+
+        assert!(started_at.elapsed() >= Duration::from_millis(90));
+    }
+}
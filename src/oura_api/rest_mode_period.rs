@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraRestModeEpisode {
+    pub tags: Vec<String>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraRestModePeriodDocument {
+    pub id: String,
+    pub end_day: Option<String>,
+    pub end_time: Option<String>,
+    pub episodes: Vec<OuraRestModeEpisode>,
+    pub start_day: String,
+    pub start_time: Option<String>,
+}
@@ -0,0 +1,111 @@
+use crate::config::OuraApiRetry;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BASE_DELAY_IN_MILLISECONDS: u64 = 500;
+const DEFAULT_MAX_DELAY_IN_MILLISECONDS: u64 = 30_000;
+const DEFAULT_RETRYABLE_STATUS_CODES: [u16; 4] = [500, 502, 503, 504];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retryable_status_codes: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: Duration::from_millis(DEFAULT_BASE_DELAY_IN_MILLISECONDS),
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_IN_MILLISECONDS),
+            retryable_status_codes: DEFAULT_RETRYABLE_STATUS_CODES.to_vec(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &Option<OuraApiRetry>) -> RetryPolicy {
+        let default = RetryPolicy::default();
+
+        match config {
+            Some(config) => RetryPolicy {
+                max_attempts: config.max_attempts.unwrap_or(default.max_attempts).max(1),
+                base_delay: config
+                    .base_delay_ms
+                    .map_or(default.base_delay, Duration::from_millis),
+                max_delay: config
+                    .max_delay_ms
+                    .map_or(default.max_delay, Duration::from_millis),
+                retryable_status_codes: config
+                    .retryable_status_codes
+                    .clone()
+                    .unwrap_or(default.retryable_status_codes),
+            },
+            None => default,
+        }
+    }
+
+    /// Exponential backoff with full jitter: the delay before the retry following the given
+    /// attempt is picked at random between zero and `base_delay * 2^(attempt - 1)`, capped at
+    /// `max_delay`. The jitter keeps the pollers of different persons from retrying in lockstep.
+    pub fn delay_after_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_delay);
+
+        ceiling.mul_f64(random_fraction())
+    }
+}
+
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+
+    (random as f64) / (u64::MAX as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::RetryPolicy;
+    use crate::config::OuraApiRetry;
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_policy_from_config() {
+        let config = Some(OuraApiRetry {
+            max_attempts: Some(5),
+            base_delay_ms: Some(100),
+            max_delay_ms: None,
+            retryable_status_codes: Some(vec![503]),
+        });
+
+        let retry_policy = RetryPolicy::from_config(&config);
+
+        assert_eq!(retry_policy.max_attempts, 5);
+        assert_eq!(retry_policy.base_delay, Duration::from_millis(100));
+        assert_eq!(retry_policy.max_delay, RetryPolicy::default().max_delay);
+        assert_eq!(retry_policy.retryable_status_codes, vec![503]);
+        assert_eq!(RetryPolicy::from_config(&None), RetryPolicy::default());
+    }
+
+    #[test]
+    fn test_delay_after_attempt_grows_exponentially_up_to_max_delay() {
+        let retry_policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            assert!(retry_policy.delay_after_attempt(1) <= Duration::from_millis(100));
+            assert!(retry_policy.delay_after_attempt(3) <= Duration::from_millis(400));
+            assert!(retry_policy.delay_after_attempt(10) <= Duration::from_millis(1000));
+            assert!(retry_policy.delay_after_attempt(100) <= Duration::from_millis(1000));
+        }
+    }
+}
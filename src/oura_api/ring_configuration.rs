@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraRingConfigurationDocument {
+    pub id: String,
+    pub color: Option<String>,
+    pub design: Option<String>,
+    pub firmware_version: Option<String>,
+    pub hardware_type: Option<String>,
+    pub set_up_at: Option<String>,
+    pub size: Option<i32>,
+}
@@ -0,0 +1,16 @@
+use super::sleep::OuraSleepMeasurement;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraSessionDocument {
+    pub id: String,
+    pub day: String,
+    pub start_datetime: String,
+    pub end_datetime: String,
+    #[serde(rename = "type")]
+    pub session_type: String,
+    pub heart_rate: Option<OuraSleepMeasurement>,
+    pub heart_rate_variability: Option<OuraSleepMeasurement>,
+    pub mood: Option<String>,
+    pub motion_count: Option<OuraSleepMeasurement>,
+}
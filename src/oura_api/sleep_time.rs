@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraOptimalBedtime {
+    pub day_tz: i32,
+    pub end_offset: i32,
+    pub start_offset: i32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraSleepTimeDocument {
+    pub id: String,
+    pub day: String,
+    pub optimal_bedtime: Option<OuraOptimalBedtime>,
+    pub recommendation: Option<String>,
+    pub status: Option<String>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraTagDocument {
+    pub id: String,
+    pub day: String,
+    pub text: Option<String>,
+    pub timestamp: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraEnhancedTagDocument {
+    pub id: String,
+    pub tag_type_code: Option<String>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub start_day: String,
+    pub end_day: Option<String>,
+    pub comment: Option<String>,
+    pub custom_name: Option<String>,
+}
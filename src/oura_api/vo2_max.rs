@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraVO2MaxDocument {
+    pub id: String,
+    pub day: String,
+    pub timestamp: String,
+    pub vo2_max: Option<f32>,
+}
@@ -0,0 +1,169 @@
+use super::{
+    map_response_into_response_error, OuraApiError, DEFAULT_OURA_API_PORT, DEFAULT_OURA_API_URL,
+    DEFAULT_TIMEOUT_IN_SECONDS,
+};
+use crate::config::OuraApi;
+use log::debug;
+use reqwest::{RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const WEBHOOK_SUBSCRIPTION_PATH: &str = "v2/webhook/subscription";
+
+/// The notification Oura sends to the callback URL when a document of a subscribed data type
+/// is created, updated or deleted.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraWebhookEvent {
+    pub event_type: String,
+    pub data_type: String,
+    pub object_id: String,
+    pub event_time: Option<String>,
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraWebhookSubscription {
+    pub id: String,
+    pub callback_url: String,
+    pub event_type: String,
+    pub data_type: String,
+    pub expiration_time: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OuraWebhookSubscriptionRequest<'a> {
+    callback_url: &'a str,
+    verification_token: &'a str,
+    event_type: &'a str,
+    data_type: &'a str,
+}
+
+/// Manages the webhook subscriptions of an Oura API application. Unlike the data endpoints, the
+/// subscription endpoints are authorized with the client credentials of the application.
+pub struct OuraWebhookClient<'a> {
+    client: reqwest::Client,
+    base_url: &'a str,
+    port: &'a str,
+    client_id: &'a str,
+    client_secret: &'a str,
+}
+
+impl OuraWebhookClient<'_> {
+    pub fn from_config<'a>(
+        config: &'a Option<OuraApi>,
+        client_id: &'a str,
+        client_secret: &'a str,
+    ) -> Result<OuraWebhookClient<'a>, OuraApiError> {
+        let base_url = match config.as_ref().and_then(|c| c.url.as_deref()) {
+            Some(url) => url,
+            None => DEFAULT_OURA_API_URL,
+        };
+        let port = match config.as_ref().and_then(|c| c.port.as_deref()) {
+            Some(port) => port,
+            None => DEFAULT_OURA_API_PORT,
+        };
+        let timeout = config
+            .as_ref()
+            .and_then(|c| c.timeout_seconds)
+            .unwrap_or(DEFAULT_TIMEOUT_IN_SECONDS);
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()
+            .map_err(|err| OuraApiError::InvalidHttpClientConfig(err.to_string()))?;
+
+        Ok(OuraWebhookClient {
+            client,
+            base_url,
+            port,
+            client_id,
+            client_secret,
+        })
+    }
+
+    fn build_url(&self, path: &str) -> String {
+        format!("{}:{}/{}", self.base_url, self.port, path)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response, OuraApiError> {
+        let response = request
+            .header("x-client-id", self.client_id)
+            .header("x-client-secret", self.client_secret)
+            .send()
+            .await
+            .map_err(OuraApiError::RequestError)?;
+
+        if !response.status().is_success() {
+            return Err(map_response_into_response_error(response).await);
+        }
+
+        Ok(response)
+    }
+
+    pub async fn list_subscriptions(&self) -> Result<Vec<OuraWebhookSubscription>, OuraApiError> {
+        let url = self.build_url(WEBHOOK_SUBSCRIPTION_PATH);
+        debug!("Listing Oura webhook subscriptions: url={}", url);
+
+        self.send(self.client.get(&url))
+            .await?
+            .json()
+            .await
+            .map_err(OuraApiError::RequestError)
+    }
+
+    pub async fn create_subscription(
+        &self,
+        callback_url: &str,
+        verification_token: &str,
+        event_type: &str,
+        data_type: &str,
+    ) -> Result<OuraWebhookSubscription, OuraApiError> {
+        let url = self.build_url(WEBHOOK_SUBSCRIPTION_PATH);
+        debug!(
+            "Creating Oura webhook subscription: url={} event_type={} data_type={}",
+            url, event_type, data_type
+        );
+
+        let subscription_request = OuraWebhookSubscriptionRequest {
+            callback_url,
+            verification_token,
+            event_type,
+            data_type,
+        };
+
+        self.send(self.client.post(&url).json(&subscription_request))
+            .await?
+            .json()
+            .await
+            .map_err(OuraApiError::RequestError)
+    }
+
+    pub async fn renew_subscription(
+        &self,
+        subscription_id: &str,
+    ) -> Result<OuraWebhookSubscription, OuraApiError> {
+        let url = self.build_url(&format!(
+            "{}/renew/{}",
+            WEBHOOK_SUBSCRIPTION_PATH, subscription_id
+        ));
+        debug!("Renewing Oura webhook subscription: url={}", url);
+
+        self.send(self.client.put(&url))
+            .await?
+            .json()
+            .await
+            .map_err(OuraApiError::RequestError)
+    }
+
+    pub async fn delete_subscription(&self, subscription_id: &str) -> Result<(), OuraApiError> {
+        let url = self.build_url(&format!(
+            "{}/{}",
+            WEBHOOK_SUBSCRIPTION_PATH, subscription_id
+        ));
+        debug!("Deleting Oura webhook subscription: url={}", url);
+
+        self.send(self.client.delete(&url)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OuraWorkoutDocument {
+    pub id: String,
+    pub activity: String,
+    pub calories: Option<f32>,
+    pub day: String,
+    pub distance: Option<f32>,
+    pub end_datetime: String,
+    pub intensity: String,
+    pub label: Option<String>,
+    pub source: String,
+    pub start_datetime: String,
+}
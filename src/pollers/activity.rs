@@ -0,0 +1,226 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraActivityDocument, OuraApiError};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ActivityContributors {
+    pub meet_daily_targets: Option<u8>,
+    pub move_every_hour: Option<u8>,
+    pub recovery_time: Option<u8>,
+    pub stay_active: Option<u8>,
+    pub training_frequency: Option<u8>,
+    pub training_volume: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Activity {
+    pub id: String,
+    pub score: Option<u8>,
+    pub steps: i32,
+    pub active_calories: i32,
+    pub total_calories: i32,
+    pub target_calories: i32,
+    pub equivalent_walking_distance: i32,
+    pub average_met_minutes: f32,
+    pub high_activity_met_minutes: i32,
+    pub medium_activity_met_minutes: i32,
+    pub low_activity_met_minutes: i32,
+    pub sedentary_met_minutes: i32,
+    pub high_activity_time: i32,
+    pub medium_activity_time: i32,
+    pub low_activity_time: i32,
+    pub sedentary_time: i32,
+    pub resting_time: i32,
+    pub non_wear_time: i32,
+    pub inactivity_alerts: i32,
+    pub contributors: ActivityContributors,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraActivityDocument {
+    pub fn try_to_activity(&self, person_name: &str) -> Result<Activity, OuraPollingError> {
+        Ok(Activity {
+            id: self.id.clone(),
+            score: self.score,
+            steps: self.steps,
+            active_calories: self.active_calories,
+            total_calories: self.total_calories,
+            target_calories: self.target_calories,
+            equivalent_walking_distance: self.equivalent_walking_distance,
+            average_met_minutes: self.average_met_minutes,
+            high_activity_met_minutes: self.high_activity_met_minutes,
+            medium_activity_met_minutes: self.medium_activity_met_minutes,
+            low_activity_met_minutes: self.low_activity_met_minutes,
+            sedentary_met_minutes: self.sedentary_met_minutes,
+            high_activity_time: self.high_activity_time,
+            medium_activity_time: self.medium_activity_time,
+            low_activity_time: self.low_activity_time,
+            sedentary_time: self.sedentary_time,
+            resting_time: self.resting_time,
+            non_wear_time: self.non_wear_time,
+            inactivity_alerts: self.inactivity_alerts,
+            contributors: ActivityContributors {
+                meet_daily_targets: self.contributors.meet_daily_targets,
+                move_every_hour: self.contributors.move_every_hour,
+                recovery_time: self.contributors.recovery_time,
+                stay_active: self.contributors.stay_active,
+                training_frequency: self.contributors.training_frequency,
+                training_volume: self.contributors.training_volume,
+            },
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub(super) fn parse_activity_data<'a>(
+    person_name: &'a str,
+    activity_documents: &'a [OuraActivityDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    activity_documents
+        .iter()
+        .map(|document| match document.try_to_activity(person_name) {
+            Ok(activity) => OuraData::Activity(activity),
+            Err(err) => OuraData::from(err),
+        })
+}
+
+pub(super) fn parse_activity_class_data<'a>(
+    person_name: &'a str,
+    activity_documents: &'a [OuraActivityDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    activity_documents.iter().flat_map(|document| {
+        document
+            .try_extract_activity_classes(person_name)
+            .map_or_else(
+                |err| vec![OuraData::from(err)],
+                |activity_classes| {
+                    activity_classes
+                        .into_iter()
+                        .map(OuraData::ActivityClass)
+                        .collect()
+                },
+            )
+    })
+}
+
+pub(super) fn parse_met_data<'a>(
+    person_name: &'a str,
+    activity_documents: &'a [OuraActivityDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    activity_documents.iter().flat_map(|document| {
+        document.try_to_met_data(person_name).map_or_else(
+            |err| vec![OuraData::from(err)],
+            |met_data| met_data.into_iter().map(OuraData::Met).collect(),
+        )
+    })
+}
+
+pub async fn poll_activity_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling activity data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let person_name = &person.person.name;
+    let activity_documents: Vec<OuraActivityDocument> = person
+        .client
+        .get_daily_activity(start_time, end_time)
+        .try_collect()
+        .await?;
+
+    let activity_data = parse_activity_data(person_name, &activity_documents);
+    let activity_class_data = parse_activity_class_data(person_name, &activity_documents);
+    let met_data = parse_met_data(person_name, &activity_documents);
+
+    let oura_data = activity_data
+        .chain(activity_class_data)
+        .chain(met_data)
+        .collect();
+
+    Ok(oura_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::{OuraActivityContributors, OuraActivityDocument};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_activity() {
+        let activity_document = OuraActivityDocument {
+            id: "activity_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            score: Some(85),
+            steps: 10000,
+            active_calories: 500,
+            total_calories: 2500,
+            high_activity_met_minutes: 30,
+            high_activity_time: 1800,
+            medium_activity_time: 3600,
+            low_activity_time: 7200,
+            contributors: OuraActivityContributors {
+                meet_daily_targets: Some(1),
+                move_every_hour: Some(2),
+                recovery_time: Some(3),
+                stay_active: Some(4),
+                training_frequency: Some(5),
+                training_volume: None,
+            },
+            ..Default::default()
+        };
+
+        let activity = activity_document.try_to_activity("test_person").unwrap();
+
+        assert_eq!(activity.id, "activity_id");
+        assert_eq!(activity.score, Some(85));
+        assert_eq!(activity.steps, 10000);
+        assert_eq!(activity.active_calories, 500);
+        assert_eq!(activity.total_calories, 2500);
+        assert_eq!(activity.high_activity_met_minutes, 30);
+        assert_eq!(activity.high_activity_time, 1800);
+        assert_eq!(activity.medium_activity_time, 3600);
+        assert_eq!(activity.low_activity_time, 7200);
+
+        assert_eq!(activity.contributors.meet_daily_targets, Some(1));
+        assert_eq!(activity.contributors.move_every_hour, Some(2));
+        assert_eq!(activity.contributors.recovery_time, Some(3));
+        assert_eq!(activity.contributors.stay_active, Some(4));
+        assert_eq!(activity.contributors.training_frequency, Some(5));
+        assert_eq!(activity.contributors.training_volume, None);
+
+        assert_eq!(
+            activity.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(activity.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_activity_with_invalid_day() {
+        let activity_document = OuraActivityDocument {
+            id: "activity_id".to_owned(),
+            day: "not-a-day".to_owned(),
+            ..Default::default()
+        };
+
+        let result = activity_document.try_to_activity("test_person");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Cannot parse Oura API date 'not-a-day': input contains invalid characters"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::oura_api::OuraActivityDocument;
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Duration, Utc};
+use std::ops::Add;
+
+#[derive(Debug)]
+pub struct ActivityClass {
+    pub activity_class: ActivityClassType,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ActivityClassType {
+    NonWear,
+    Rest,
+    Inactive,
+    LowActivity,
+    MediumActivity,
+    HighActivity,
+}
+
+impl TryFrom<char> for ActivityClassType {
+    type Error = OuraPollingError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0' => Ok(ActivityClassType::NonWear),
+            '1' => Ok(ActivityClassType::Rest),
+            '2' => Ok(ActivityClassType::Inactive),
+            '3' => Ok(ActivityClassType::LowActivity),
+            '4' => Ok(ActivityClassType::MediumActivity),
+            '5' => Ok(ActivityClassType::HighActivity),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "ActivityClassType".to_string(),
+                variant: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl OuraActivityDocument {
+    pub fn try_extract_activity_classes(
+        &self,
+        person_name: &str,
+    ) -> Result<Vec<ActivityClass>, OuraPollingError> {
+        let mut timestamp = self.timestamp.try_parse_oura_timestamp()?;
+        let mut activity_classes: Vec<ActivityClass> = Vec::new();
+
+        match &self.class_5_min {
+            Some(activity_class) => {
+                for activity_class_char in activity_class.chars() {
+                    activity_classes.push(ActivityClass {
+                        activity_class: activity_class_char.try_into()?,
+                        timestamp,
+                        person_name: person_name.to_owned(),
+                    });
+
+                    timestamp = timestamp.add(Duration::minutes(5));
+                }
+
+                Ok(activity_classes)
+            }
+            None => Err(OuraPollingError::NoActivityClassDataFoundError {
+                activity_id: self.id.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_try_from_char_for_activity_class_type() {
+        assert_eq!(ActivityClassType::NonWear, '0'.try_into().unwrap());
+        assert_eq!(ActivityClassType::Rest, '1'.try_into().unwrap());
+        assert_eq!(ActivityClassType::Inactive, '2'.try_into().unwrap());
+        assert_eq!(ActivityClassType::LowActivity, '3'.try_into().unwrap());
+        assert_eq!(ActivityClassType::MediumActivity, '4'.try_into().unwrap());
+        assert_eq!(ActivityClassType::HighActivity, '5'.try_into().unwrap());
+
+        let error = ActivityClassType::try_from('6').unwrap_err();
+        assert_eq!(error.to_string(), "Unknown ActivityClassType: '6'");
+    }
+
+    #[test]
+    fn test_try_extract_activity_classes() {
+        let oura_activity_document = OuraActivityDocument {
+            id: "id".to_owned(),
+            timestamp: "2023-06-22T04:00:00+03:00".to_string(),
+            class_5_min: Some("025".to_owned()),
+            ..Default::default()
+        };
+
+        let activity_classes = oura_activity_document
+            .try_extract_activity_classes("person")
+            .unwrap();
+
+        assert_eq!(3, activity_classes.len());
+
+        assert_eq!(
+            ActivityClassType::NonWear,
+            activity_classes[0].activity_class
+        );
+        assert_eq!("person", activity_classes[0].person_name);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T04:00:00+03:00").unwrap(),
+            activity_classes[0].timestamp
+        );
+
+        assert_eq!(
+            ActivityClassType::Inactive,
+            activity_classes[1].activity_class
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T04:05:00+03:00").unwrap(),
+            activity_classes[1].timestamp
+        );
+
+        assert_eq!(
+            ActivityClassType::HighActivity,
+            activity_classes[2].activity_class
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T04:10:00+03:00").unwrap(),
+            activity_classes[2].timestamp
+        );
+    }
+
+    #[test]
+    fn test_try_extract_activity_classes_without_class_data() {
+        let oura_activity_document = OuraActivityDocument {
+            id: "id".to_owned(),
+            timestamp: "2023-06-22T04:00:00+03:00".to_string(),
+            class_5_min: None,
+            ..Default::default()
+        };
+
+        let error = oura_activity_document
+            .try_extract_activity_classes("person")
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "No activity class data found for activity document with id: 'id'"
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BaselineMetric {
+    RestingHeartRate,
+    AverageHrv,
+    RespiratoryRate,
+    TemperatureDeviation,
+    ReadinessScore,
+}
+
+impl Display for BaselineMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let metric = match self {
+            BaselineMetric::RestingHeartRate => "resting_heart_rate",
+            BaselineMetric::AverageHrv => "average_hrv",
+            BaselineMetric::RespiratoryRate => "respiratory_rate",
+            BaselineMetric::TemperatureDeviation => "temperature_deviation",
+            BaselineMetric::ReadinessScore => "readiness_score",
+        };
+
+        write!(f, "{}", metric)
+    }
+}
+
+/// The value of a metric on a day compared to its rolling baseline over the preceding
+/// `window_in_days` days. The z-score is left out when the baseline has too few samples or no
+/// variation.
+#[derive(Debug)]
+pub struct Baseline {
+    pub day: NaiveDate,
+    pub metric: BaselineMetric,
+    pub window_in_days: u32,
+    pub value: f32,
+    pub mean: f32,
+    pub standard_deviation: f32,
+    pub z_score: Option<f32>,
+    pub samples: u32,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
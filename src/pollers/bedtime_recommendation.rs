@@ -0,0 +1,285 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraSleepTimeDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Duration, Utc};
+use futures::TryStreamExt;
+use log::info;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum SleepTimeRecommendation {
+    ImproveEfficiency,
+    EarlierBedtime,
+    LaterBedtime,
+    EarlierWakeUpTime,
+    LaterWakeUpTime,
+    FollowOptimalBedtime,
+}
+
+impl FromStr for SleepTimeRecommendation {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "improve_efficiency" => Ok(SleepTimeRecommendation::ImproveEfficiency),
+            "earlier_bedtime" => Ok(SleepTimeRecommendation::EarlierBedtime),
+            "later_bedtime" => Ok(SleepTimeRecommendation::LaterBedtime),
+            "earlier_wake_up_time" => Ok(SleepTimeRecommendation::EarlierWakeUpTime),
+            "later_wake_up_time" => Ok(SleepTimeRecommendation::LaterWakeUpTime),
+            "follow_optimal_bedtime" => Ok(SleepTimeRecommendation::FollowOptimalBedtime),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "SleepTimeRecommendation".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SleepTimeRecommendation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SleepTimeRecommendation::ImproveEfficiency => write!(f, "improve_efficiency"),
+            SleepTimeRecommendation::EarlierBedtime => write!(f, "earlier_bedtime"),
+            SleepTimeRecommendation::LaterBedtime => write!(f, "later_bedtime"),
+            SleepTimeRecommendation::EarlierWakeUpTime => write!(f, "earlier_wake_up_time"),
+            SleepTimeRecommendation::LaterWakeUpTime => write!(f, "later_wake_up_time"),
+            SleepTimeRecommendation::FollowOptimalBedtime => write!(f, "follow_optimal_bedtime"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SleepTimeStatus {
+    NotEnoughNights,
+    NotEnoughRecentNights,
+    BadSleepQuality,
+    OnlyRecommendedFound,
+    OptimalFound,
+}
+
+impl FromStr for SleepTimeStatus {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "not_enough_nights" => Ok(SleepTimeStatus::NotEnoughNights),
+            "not_enough_recent_nights" => Ok(SleepTimeStatus::NotEnoughRecentNights),
+            "bad_sleep_quality" => Ok(SleepTimeStatus::BadSleepQuality),
+            "only_recommended_found" => Ok(SleepTimeStatus::OnlyRecommendedFound),
+            "optimal_found" => Ok(SleepTimeStatus::OptimalFound),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "SleepTimeStatus".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SleepTimeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SleepTimeStatus::NotEnoughNights => write!(f, "not_enough_nights"),
+            SleepTimeStatus::NotEnoughRecentNights => write!(f, "not_enough_recent_nights"),
+            SleepTimeStatus::BadSleepQuality => write!(f, "bad_sleep_quality"),
+            SleepTimeStatus::OnlyRecommendedFound => write!(f, "only_recommended_found"),
+            SleepTimeStatus::OptimalFound => write!(f, "optimal_found"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OptimalBedtime {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub start_offset: i32,
+    pub end_offset: i32,
+}
+
+#[derive(Debug)]
+pub struct BedtimeRecommendation {
+    pub id: String,
+    pub recommendation: Option<SleepTimeRecommendation>,
+    pub status: Option<SleepTimeStatus>,
+    pub optimal_bedtime: Option<OptimalBedtime>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraSleepTimeDocument {
+    pub fn try_to_bedtime_recommendation(
+        &self,
+        person_name: &str,
+    ) -> Result<BedtimeRecommendation, OuraPollingError> {
+        let day = self.day.try_parse_oura_date_as_timestamp()?;
+        let recommendation = match &self.recommendation {
+            Some(recommendation) => Some(recommendation.parse()?),
+            None => None,
+        };
+        let status = match &self.status {
+            Some(status) => Some(status.parse()?),
+            None => None,
+        };
+
+        // The offsets are in seconds relative to the local midnight of the day, and `day_tz` is
+        // the offset of the local time zone from UTC in seconds.
+        let optimal_bedtime = self.optimal_bedtime.as_ref().map(|optimal_bedtime| {
+            let local_midnight = day - Duration::seconds(optimal_bedtime.day_tz.into());
+
+            OptimalBedtime {
+                start: local_midnight + Duration::seconds(optimal_bedtime.start_offset.into()),
+                end: local_midnight + Duration::seconds(optimal_bedtime.end_offset.into()),
+                start_offset: optimal_bedtime.start_offset,
+                end_offset: optimal_bedtime.end_offset,
+            }
+        });
+
+        Ok(BedtimeRecommendation {
+            id: self.id.clone(),
+            recommendation,
+            status,
+            optimal_bedtime,
+            timestamp: day,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_bedtime_recommendation_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling bedtime recommendation data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let bedtime_recommendation_data: Vec<OuraData> = person
+        .client
+        .get_sleep_times(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_bedtime_recommendation(&person.person.name) {
+                Ok(bedtime_recommendation) => {
+                    OuraData::BedtimeRecommendation(bedtime_recommendation)
+                }
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(bedtime_recommendation_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SleepTimeRecommendation, SleepTimeStatus};
+    use crate::oura_api::{OuraOptimalBedtime, OuraSleepTimeDocument};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_sleep_time_recommendation_from_str() {
+        assert_eq!(
+            "improve_efficiency"
+                .parse::<SleepTimeRecommendation>()
+                .unwrap(),
+            SleepTimeRecommendation::ImproveEfficiency
+        );
+        assert_eq!(
+            "follow_optimal_bedtime"
+                .parse::<SleepTimeRecommendation>()
+                .unwrap(),
+            SleepTimeRecommendation::FollowOptimalBedtime
+        );
+
+        let error = "sleep_more".parse::<SleepTimeRecommendation>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown SleepTimeRecommendation: 'sleep_more'"
+        );
+    }
+
+    #[test]
+    fn test_sleep_time_status_from_str() {
+        assert_eq!(
+            "optimal_found".parse::<SleepTimeStatus>().unwrap(),
+            SleepTimeStatus::OptimalFound
+        );
+        assert_eq!(
+            "not_enough_nights".parse::<SleepTimeStatus>().unwrap(),
+            SleepTimeStatus::NotEnoughNights
+        );
+
+        let error = "great".parse::<SleepTimeStatus>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown SleepTimeStatus: 'great'");
+    }
+
+    #[test]
+    fn test_try_to_bedtime_recommendation() {
+        let sleep_time_document = OuraSleepTimeDocument {
+            id: "sleep_time_id".to_owned(),
+            day: "2021-01-02".to_owned(),
+            optimal_bedtime: Some(OuraOptimalBedtime {
+                day_tz: 7200,
+                start_offset: -3600,
+                end_offset: 1800,
+            }),
+            recommendation: Some("follow_optimal_bedtime".to_owned()),
+            status: Some("optimal_found".to_owned()),
+        };
+
+        let bedtime_recommendation = sleep_time_document
+            .try_to_bedtime_recommendation("test_person")
+            .unwrap();
+
+        assert_eq!(bedtime_recommendation.id, "sleep_time_id");
+        assert_eq!(
+            bedtime_recommendation.recommendation,
+            Some(SleepTimeRecommendation::FollowOptimalBedtime)
+        );
+        assert_eq!(
+            bedtime_recommendation.status,
+            Some(SleepTimeStatus::OptimalFound)
+        );
+
+        let optimal_bedtime = bedtime_recommendation.optimal_bedtime.unwrap();
+        assert_eq!(
+            optimal_bedtime.start,
+            "2021-01-01T23:00:00+02:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            optimal_bedtime.end,
+            "2021-01-02T00:30:00+02:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            bedtime_recommendation.timestamp,
+            "2021-01-02T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(bedtime_recommendation.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_bedtime_recommendation_without_optimal_bedtime() {
+        let sleep_time_document = OuraSleepTimeDocument {
+            id: "sleep_time_id".to_owned(),
+            day: "2021-01-02".to_owned(),
+            status: Some("not_enough_nights".to_owned()),
+            ..Default::default()
+        };
+
+        let bedtime_recommendation = sleep_time_document
+            .try_to_bedtime_recommendation("test_person")
+            .unwrap();
+
+        assert_eq!(bedtime_recommendation.recommendation, None);
+        assert_eq!(bedtime_recommendation.optimal_bedtime, None);
+    }
+}
@@ -0,0 +1,79 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailyCardiovascularAgeDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug)]
+pub struct CardiovascularAge {
+    pub vascular_age: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraDailyCardiovascularAgeDocument {
+    pub fn try_to_cardiovascular_age(
+        &self,
+        person_name: &str,
+    ) -> Result<CardiovascularAge, OuraPollingError> {
+        Ok(CardiovascularAge {
+            vascular_age: self.vascular_age,
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_cardiovascular_age_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling cardiovascular age data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let cardiovascular_age_data: Vec<OuraData> = person
+        .client
+        .get_daily_cardiovascular_age(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_cardiovascular_age(&person.person.name) {
+                Ok(cardiovascular_age) => OuraData::CardiovascularAge(cardiovascular_age),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(cardiovascular_age_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::OuraDailyCardiovascularAgeDocument;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_cardiovascular_age() {
+        let cardiovascular_age_document = OuraDailyCardiovascularAgeDocument {
+            day: "2021-01-01".to_owned(),
+            vascular_age: Some(34),
+        };
+
+        let cardiovascular_age = cardiovascular_age_document
+            .try_to_cardiovascular_age("test_person")
+            .unwrap();
+
+        assert_eq!(cardiovascular_age.vascular_age, Some(34));
+        assert_eq!(
+            cardiovascular_age.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(cardiovascular_age.person_name, "test_person");
+    }
+}
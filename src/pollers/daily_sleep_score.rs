@@ -0,0 +1,123 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailySleepDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug)]
+pub struct DailySleepContributors {
+    pub deep_sleep: Option<u8>,
+    pub efficiency: Option<u8>,
+    pub latency: Option<u8>,
+    pub rem_sleep: Option<u8>,
+    pub restfulness: Option<u8>,
+    pub timing: Option<u8>,
+    pub total_sleep: Option<u8>,
+}
+
+#[derive(Debug)]
+pub struct DailySleepScore {
+    pub id: String,
+    pub score: Option<u8>,
+    pub contributors: DailySleepContributors,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraDailySleepDocument {
+    pub fn try_to_daily_sleep_score(
+        &self,
+        person_name: &str,
+    ) -> Result<DailySleepScore, OuraPollingError> {
+        Ok(DailySleepScore {
+            id: self.id.clone(),
+            score: self.score,
+            contributors: DailySleepContributors {
+                deep_sleep: self.contributors.deep_sleep,
+                efficiency: self.contributors.efficiency,
+                latency: self.contributors.latency,
+                rem_sleep: self.contributors.rem_sleep,
+                restfulness: self.contributors.restfulness,
+                timing: self.contributors.timing,
+                total_sleep: self.contributors.total_sleep,
+            },
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_daily_sleep_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling daily sleep data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let daily_sleep_data: Vec<OuraData> = person
+        .client
+        .get_daily_sleep(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_daily_sleep_score(&person.person.name) {
+                Ok(daily_sleep_score) => OuraData::DailySleepScore(daily_sleep_score),
+                Err(parsing_error) => OuraData::from(parsing_error),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(daily_sleep_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::{OuraDailySleepContributors, OuraDailySleepDocument};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_daily_sleep_score() {
+        let daily_sleep_document = OuraDailySleepDocument {
+            id: "daily_sleep_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            score: Some(78),
+            contributors: OuraDailySleepContributors {
+                deep_sleep: Some(1),
+                efficiency: Some(2),
+                latency: Some(3),
+                rem_sleep: Some(4),
+                restfulness: Some(5),
+                timing: Some(6),
+                total_sleep: None,
+            },
+            ..Default::default()
+        };
+
+        let daily_sleep_score = daily_sleep_document
+            .try_to_daily_sleep_score("test_person")
+            .unwrap();
+
+        assert_eq!(daily_sleep_score.id, "daily_sleep_id");
+        assert_eq!(daily_sleep_score.score, Some(78));
+
+        assert_eq!(daily_sleep_score.contributors.deep_sleep, Some(1));
+        assert_eq!(daily_sleep_score.contributors.efficiency, Some(2));
+        assert_eq!(daily_sleep_score.contributors.latency, Some(3));
+        assert_eq!(daily_sleep_score.contributors.rem_sleep, Some(4));
+        assert_eq!(daily_sleep_score.contributors.restfulness, Some(5));
+        assert_eq!(daily_sleep_score.contributors.timing, Some(6));
+        assert_eq!(daily_sleep_score.contributors.total_sleep, None);
+
+        assert_eq!(
+            daily_sleep_score.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(daily_sleep_score.person_name, "test_person");
+    }
+}
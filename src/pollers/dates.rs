@@ -1,11 +1,12 @@
 use super::errors::OuraPollingError;
 use chrono::{DateTime, NaiveDate, Utc};
 
-const OURA_API_DATE_FORMAT: &'static str = "%Y-%m-%d";
+const OURA_API_DATE_FORMAT: &str = "%Y-%m-%d";
 
 pub trait TryOuraTimeStringParsing {
     fn try_parse_oura_timestamp(&self) -> Result<DateTime<Utc>, OuraPollingError>;
     fn try_parse_oura_date(&self) -> Result<NaiveDate, OuraPollingError>;
+    fn try_parse_oura_date_as_timestamp(&self) -> Result<DateTime<Utc>, OuraPollingError>;
 }
 
 impl TryOuraTimeStringParsing for String {
@@ -27,6 +28,18 @@ impl TryOuraTimeStringParsing for String {
             }
         })
     }
+
+    fn try_parse_oura_date_as_timestamp(&self) -> Result<DateTime<Utc>, OuraPollingError> {
+        let timestamp = self
+            .try_parse_oura_date()?
+            .and_hms_opt(0, 0, 0)
+            .ok_or(OuraPollingError::UnexpectedError(String::from(
+                "Cannot construct a NaiveDate from parsed oura date",
+            )))?
+            .and_utc();
+
+        Ok(timestamp)
+    }
 }
 
 #[cfg(test)]
@@ -39,10 +52,7 @@ mod tests {
     fn test_try_parse_oura_timestamp() {
         let timestamp = "2021-01-01T00:00:00+00:00".to_string();
         let datetime = timestamp.try_parse_oura_timestamp().unwrap();
-        assert_eq!(
-            datetime,
-            Utc.with_ymd_and_hms(2021, 01, 01, 0, 0, 0).unwrap()
-        );
+        assert_eq!(datetime, Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap());
     }
 
     #[test]
@@ -52,6 +62,13 @@ mod tests {
         assert_eq!(naive_date, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
     }
 
+    #[test]
+    fn test_try_parse_oura_date_as_timestamp() {
+        let date = "2021-01-01".to_string();
+        let datetime = date.try_parse_oura_date_as_timestamp().unwrap();
+        assert_eq!(datetime, Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap());
+    }
+
     #[test]
     fn test_try_parse_oura_date_error() {
         let date = "2021-01-01T00:00:00+00:00".to_string();
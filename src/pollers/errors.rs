@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum OuraPollingError {
     #[error("Cannot parse Oura API timestamp '{timestamp}': {source}")]
     TimestampParsingError {
@@ -28,12 +29,21 @@ pub enum OuraPollingError {
     #[error("No sleep phase data found for sleep document with id: '{sleep_id}'")]
     NoSleepPhaseDataFoundError { sleep_id: String },
 
+    #[error("Invalid movement '{movement}' in sleep document with id: '{sleep_id}'")]
+    InvalidSleepMovementError { sleep_id: String, movement: String },
+
     #[error("No heart rate data found for sleep document with id: '{sleep_id}'")]
     NoHeartRateDataFoundError { sleep_id: String },
 
     #[error("No readiness score found for sleep document with id: '{sleep_id}'")]
     NoReadinessScoreFoundError { sleep_id: String },
 
+    #[error("No readiness score found for daily readiness document with id: '{readiness_id}'")]
+    NoDailyReadinessScoreFoundError { readiness_id: String },
+
+    #[error("No activity class data found for activity document with id: '{activity_id}'")]
+    NoActivityClassDataFoundError { activity_id: String },
+
     #[error("Something went wrong when polling Oura data: {0}")]
     UnexpectedError(String),
 }
@@ -4,6 +4,7 @@ use crate::oura_api::{OuraHeartRateData, OuraSleepDocument};
 use crate::pollers::dates::TryOuraTimeStringParsing;
 use crate::pollers::errors::OuraPollingError;
 use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -74,8 +75,7 @@ impl OuraSleepDocument {
     pub fn try_to_heart_rate_data(&self, person: &str) -> Result<Vec<HeartRate>, OuraPollingError> {
         match &self.heart_rate {
             Some(heart_rate) => {
-                let heart_rate_measurement_interval_in_seconds =
-                    heart_rate.interval.round() as i64;
+                let heart_rate_measurement_interval_in_seconds = heart_rate.interval.round() as i64;
 
                 let mut heart_rate_data: Vec<HeartRate> = Vec::new();
                 let mut timestamp = heart_rate.timestamp.try_parse_oura_timestamp()?;
@@ -97,7 +97,9 @@ impl OuraSleepDocument {
 
                 Ok(heart_rate_data)
             }
-            None => Err(OuraPollingError::NoHeartRateDataFoundError { sleep_id: self.id.to_string() })
+            None => Err(OuraPollingError::NoHeartRateDataFoundError {
+                sleep_id: self.id.to_string(),
+            }),
         }
     }
 }
@@ -111,22 +113,19 @@ pub async fn poll_heart_rate_data(
         "Polling heart rate data for '{}' from {} to {}",
         person.person.name, start_time, end_time
     );
-    let response = person
+    let heart_rate_data: Vec<OuraData> = person
         .client
         .get_heart_rate_data(start_time, end_time)
-        .await;
-    let heart_rate_data: Vec<OuraData> = response?
-        .data
-        .iter()
-        .map(
+        .map_ok(
             |raw| match raw.try_to_heart_rate_data(&person.person.name) {
                 Ok(data) => OuraData::HeartRate(data),
                 Err(parsing_error) => OuraData::from(parsing_error),
             },
         )
-        .collect();
+        .try_collect()
+        .await?;
 
-    return Ok(heart_rate_data);
+    Ok(heart_rate_data)
 }
 
 #[cfg(test)]
@@ -0,0 +1,78 @@
+use crate::oura_api::OuraActivityDocument;
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use std::ops::Add;
+
+#[derive(Debug)]
+pub struct Met {
+    pub met: f32,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraActivityDocument {
+    pub fn try_to_met_data(&self, person: &str) -> Result<Vec<Met>, OuraPollingError> {
+        let met_measurement_interval_in_seconds = self.met.interval.round() as i64;
+
+        let mut met_data: Vec<Met> = Vec::new();
+        let mut timestamp = self.met.timestamp.try_parse_oura_timestamp()?;
+
+        for item in &self.met.items {
+            if let Some(met) = item {
+                met_data.push(Met {
+                    met: *met,
+                    timestamp,
+                    person_name: person.to_owned(),
+                });
+            }
+
+            timestamp = timestamp.add(chrono::Duration::seconds(
+                met_measurement_interval_in_seconds,
+            ));
+        }
+
+        Ok(met_data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::{OuraActivityDocument, OuraSleepMeasurement};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_met_data() {
+        let activity_document = OuraActivityDocument {
+            id: "test_id".to_owned(),
+            met: OuraSleepMeasurement {
+                interval: 60.0,
+                timestamp: "2021-01-01T04:00:00+00:00".to_owned(),
+                items: vec![Some(0.9), None, Some(3.5)],
+            },
+            ..Default::default()
+        };
+
+        let met_data = activity_document.try_to_met_data("test_person").unwrap();
+
+        assert_eq!(met_data.len(), 2);
+        assert_eq!(met_data[0].met, 0.9);
+        assert_eq!(met_data[1].met, 3.5);
+
+        assert_eq!(met_data[0].person_name, "test_person");
+        assert_eq!(met_data[1].person_name, "test_person");
+
+        assert_eq!(
+            met_data[0].timestamp,
+            "2021-01-01T04:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            met_data[1].timestamp,
+            "2021-01-01T04:02:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+    }
+}
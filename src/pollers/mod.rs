@@ -1,24 +1,78 @@
+mod activity;
+mod activity_class;
+mod baseline;
+mod bedtime_recommendation;
+mod cardiovascular_age;
+mod daily_sleep_score;
 mod dates;
 mod errors;
 mod heart_rate;
 mod hrv;
+mod met;
+mod person_metadata;
 mod readiness;
+mod resilience;
+mod rest_mode_period;
+mod session;
 mod sleep;
+mod sleep_architecture;
+mod sleep_movement;
 mod sleep_phase;
+mod sleep_summary;
+mod spo2;
+mod stress;
+mod tag;
+mod vo2_max;
+mod webhook;
+mod workout;
 
 use crate::config::{OuraApi, OuraPerson};
 use crate::oura_api::{OuraApiError, OuraHttpClient};
 use crate::pollers::sleep::poll_sleep_data;
+use activity::poll_activity_data;
+use bedtime_recommendation::poll_bedtime_recommendation_data;
+use cardiovascular_age::poll_cardiovascular_age_data;
 use chrono::{DateTime, Utc};
-use futures::stream::{select, select_all};
+use daily_sleep_score::poll_daily_sleep_data;
+use futures::stream::select_all;
 use futures::{stream, FutureExt, Stream, StreamExt};
 use heart_rate::poll_heart_rate_data;
+use person_metadata::poll_person_metadata;
+use readiness::poll_readiness_data;
+use resilience::poll_resilience_data;
+use rest_mode_period::poll_rest_mode_period_data;
+use session::poll_session_data;
+use spo2::poll_spo2_data;
+use stress::poll_stress_data;
+use tag::poll_tag_data;
+use vo2_max::poll_vo2_max_data;
+use workout::poll_workout_data;
 
+pub use activity::Activity;
+pub use activity_class::{ActivityClass, ActivityClassType};
+pub use baseline::{Baseline, BaselineMetric};
+pub use bedtime_recommendation::BedtimeRecommendation;
+pub use cardiovascular_age::CardiovascularAge;
+pub use daily_sleep_score::DailySleepScore;
 pub use heart_rate::HeartRate;
 pub use hrv::HeartRateVariability;
+pub use met::Met;
+pub use person_metadata::PersonMetadata;
 pub use readiness::Readiness;
-pub use sleep::Sleep;
-pub use sleep_phase::{SleepPhase, SleepPhaseType};
+pub use resilience::Resilience;
+pub use rest_mode_period::RestModePeriod;
+pub use session::{Session, SessionSample, SessionSampleType};
+pub use sleep::{Sleep, SleepType};
+pub use sleep_architecture::SleepArchitecture;
+pub use sleep_movement::SleepMovement;
+pub use sleep_phase::{SleepPhase, SleepPhaseSegment, SleepPhaseType};
+pub use sleep_summary::{SleepSeriesSummary, SleepSeriesType};
+pub use spo2::SpO2;
+pub use stress::Stress;
+pub use tag::Tag;
+pub use vo2_max::VO2Max;
+pub use webhook::WEBHOOK_DATA_TYPES;
+pub use workout::Workout;
 
 use self::errors::OuraPollingError;
 
@@ -28,8 +82,28 @@ pub enum OuraData {
     HeartRateVariability(HeartRateVariability),
     Sleep(Sleep),
     SleepPhase(SleepPhase),
-    Activity,
+    SleepPhaseSegment(SleepPhaseSegment),
+    SleepMovement(SleepMovement),
+    SleepArchitecture(SleepArchitecture),
+    SleepSeriesSummary(SleepSeriesSummary),
+    Activity(Activity),
+    ActivityClass(ActivityClass),
+    Met(Met),
     Readiness(Readiness),
+    DailySleepScore(DailySleepScore),
+    Workout(Workout),
+    Session(Session),
+    SessionSample(SessionSample),
+    SpO2(SpO2),
+    Stress(Stress),
+    Resilience(Resilience),
+    CardiovascularAge(CardiovascularAge),
+    VO2Max(VO2Max),
+    Tag(Tag),
+    RestModePeriod(RestModePeriod),
+    PersonMetadata(PersonMetadata),
+    BedtimeRecommendation(BedtimeRecommendation),
+    Baseline(Baseline),
     Error { message: String },
 }
 
@@ -40,8 +114,76 @@ impl OuraData {
             OuraData::HeartRateVariability(hrv) => Some(hrv.timestamp),
             OuraData::Sleep(sleep) => Some(sleep.bedtime_end),
             OuraData::SleepPhase(sleep_phase) => Some(sleep_phase.timestamp),
+            OuraData::SleepPhaseSegment(sleep_phase_segment) => {
+                Some(sleep_phase_segment.start_datetime)
+            }
+            OuraData::SleepMovement(sleep_movement) => Some(sleep_movement.timestamp),
+            OuraData::SleepArchitecture(sleep_architecture) => Some(sleep_architecture.timestamp),
+            OuraData::SleepSeriesSummary(sleep_series_summary) => {
+                Some(sleep_series_summary.timestamp)
+            }
             OuraData::Readiness(readiness) => Some(readiness.timestamp),
-            OuraData::Activity => None,
+            OuraData::DailySleepScore(daily_sleep_score) => Some(daily_sleep_score.timestamp),
+            OuraData::Activity(activity) => Some(activity.timestamp),
+            OuraData::ActivityClass(activity_class) => Some(activity_class.timestamp),
+            OuraData::Met(met) => Some(met.timestamp),
+            OuraData::Workout(workout) => Some(workout.start_datetime),
+            OuraData::Session(session) => Some(session.start_datetime),
+            OuraData::SessionSample(session_sample) => Some(session_sample.timestamp),
+            OuraData::SpO2(spo2) => Some(spo2.timestamp),
+            OuraData::Stress(stress) => Some(stress.timestamp),
+            OuraData::Resilience(resilience) => Some(resilience.timestamp),
+            OuraData::CardiovascularAge(cardiovascular_age) => Some(cardiovascular_age.timestamp),
+            OuraData::VO2Max(vo2_max) => Some(vo2_max.timestamp),
+            OuraData::Tag(tag) => Some(tag.start_datetime),
+            OuraData::RestModePeriod(rest_mode_period) => Some(rest_mode_period.start_datetime),
+            OuraData::PersonMetadata(_) => None,
+            OuraData::BedtimeRecommendation(bedtime_recommendation) => {
+                Some(bedtime_recommendation.timestamp)
+            }
+            OuraData::Baseline(baseline) => Some(baseline.timestamp),
+            OuraData::Error { .. } => None,
+        }
+    }
+
+    pub fn get_person_name(&self) -> Option<&str> {
+        match self {
+            OuraData::HeartRate(heart_rate) => Some(&heart_rate.person_name),
+            OuraData::HeartRateVariability(hrv) => Some(&hrv.person_name),
+            OuraData::Sleep(sleep) => Some(&sleep.person_name),
+            OuraData::SleepPhase(sleep_phase) => Some(&sleep_phase.person_name),
+            OuraData::SleepPhaseSegment(sleep_phase_segment) => {
+                Some(&sleep_phase_segment.person_name)
+            }
+            OuraData::SleepMovement(sleep_movement) => Some(&sleep_movement.person_name),
+            OuraData::SleepArchitecture(sleep_architecture) => {
+                Some(&sleep_architecture.person_name)
+            }
+            OuraData::SleepSeriesSummary(sleep_series_summary) => {
+                Some(&sleep_series_summary.person_name)
+            }
+            OuraData::Readiness(readiness) => Some(&readiness.person_name),
+            OuraData::DailySleepScore(daily_sleep_score) => Some(&daily_sleep_score.person_name),
+            OuraData::Activity(activity) => Some(&activity.person_name),
+            OuraData::ActivityClass(activity_class) => Some(&activity_class.person_name),
+            OuraData::Met(met) => Some(&met.person_name),
+            OuraData::Workout(workout) => Some(&workout.person_name),
+            OuraData::Session(session) => Some(&session.person_name),
+            OuraData::SessionSample(session_sample) => Some(&session_sample.person_name),
+            OuraData::SpO2(spo2) => Some(&spo2.person_name),
+            OuraData::Stress(stress) => Some(&stress.person_name),
+            OuraData::Resilience(resilience) => Some(&resilience.person_name),
+            OuraData::CardiovascularAge(cardiovascular_age) => {
+                Some(&cardiovascular_age.person_name)
+            }
+            OuraData::VO2Max(vo2_max) => Some(&vo2_max.person_name),
+            OuraData::Tag(tag) => Some(&tag.person_name),
+            OuraData::RestModePeriod(rest_mode_period) => Some(&rest_mode_period.person_name),
+            OuraData::PersonMetadata(person_metadata) => Some(&person_metadata.person_name),
+            OuraData::BedtimeRecommendation(bedtime_recommendation) => {
+                Some(&bedtime_recommendation.person_name)
+            }
+            OuraData::Baseline(baseline) => Some(&baseline.person_name),
             OuraData::Error { .. } => None,
         }
     }
@@ -49,17 +191,17 @@ impl OuraData {
 
 impl From<OuraPollingError> for OuraData {
     fn from(error: OuraPollingError) -> OuraData {
-        return OuraData::Error {
+        OuraData::Error {
             message: format!("{}", error),
-        };
+        }
     }
 }
 
 impl From<OuraApiError> for OuraData {
     fn from(error: OuraApiError) -> OuraData {
-        return OuraData::Error {
+        OuraData::Error {
             message: format!("{}", error),
-        };
+        }
     }
 }
 
@@ -70,12 +212,14 @@ struct PollerPerson<'a> {
 
 pub struct Poller<'a> {
     persons: Vec<PollerPerson<'a>>,
+    poll_person_metadata: bool,
 }
 
 impl Poller<'_> {
     pub fn initialize_with_persons<'a>(
-        persons: &'a Vec<OuraPerson>,
+        persons: &'a [OuraPerson],
         http_client_config: &'a Option<OuraApi>,
+        poll_person_metadata: bool,
     ) -> Result<Poller<'a>, OuraApiError> {
         let poller_persons: Result<Vec<PollerPerson>, OuraApiError> = persons
             .iter()
@@ -89,6 +233,7 @@ impl Poller<'_> {
 
         Ok(Poller {
             persons: poller_persons?,
+            poll_person_metadata,
         })
     }
 
@@ -97,18 +242,42 @@ impl Poller<'_> {
         start_time: &'a DateTime<Utc>,
         end_time: &'a DateTime<Utc>,
     ) -> impl Stream<Item = OuraData> + 'a {
-        let pollers = select_all(self.persons.iter().map(|person| {
-            let sleep_data_stream =
-                Box::pin(poll_sleep_data(person, start_time, end_time).into_stream());
-            let heart_rate_data_stream =
-                Box::pin(poll_heart_rate_data(person, start_time, end_time).into_stream());
+        // Person metadata is polled before anything else so that it is known by the time the
+        // rest of the data gets exported.
+        let person_metadata_pollers = select_all(
+            self.persons
+                .iter()
+                .filter(|_| self.poll_person_metadata)
+                .map(|person| poll_person_metadata(person).boxed().into_stream()),
+        );
 
-            return select(sleep_data_stream, heart_rate_data_stream);
+        let pollers = select_all(self.persons.iter().flat_map(|person| {
+            vec![
+                poll_sleep_data(person, start_time, end_time).boxed(),
+                poll_heart_rate_data(person, start_time, end_time).boxed(),
+                poll_activity_data(person, start_time, end_time).boxed(),
+                poll_daily_sleep_data(person, start_time, end_time).boxed(),
+                poll_readiness_data(person, start_time, end_time).boxed(),
+                poll_workout_data(person, start_time, end_time).boxed(),
+                poll_session_data(person, start_time, end_time).boxed(),
+                poll_spo2_data(person, start_time, end_time).boxed(),
+                poll_stress_data(person, start_time, end_time).boxed(),
+                poll_resilience_data(person, start_time, end_time).boxed(),
+                poll_cardiovascular_age_data(person, start_time, end_time).boxed(),
+                poll_vo2_max_data(person, start_time, end_time).boxed(),
+                poll_tag_data(person, start_time, end_time).boxed(),
+                poll_rest_mode_period_data(person, start_time, end_time).boxed(),
+                poll_bedtime_recommendation_data(person, start_time, end_time).boxed(),
+            ]
+            .into_iter()
+            .map(FutureExt::into_stream)
         }));
 
-        return pollers.flat_map(|data| match data {
-            Ok(data) => stream::iter(data),
-            Err(err) => stream::iter(vec![OuraData::from(err)]),
-        });
+        person_metadata_pollers
+            .chain(pollers)
+            .flat_map(|data| match data {
+                Ok(data) => stream::iter(data),
+                Err(err) => stream::iter(vec![OuraData::from(err)]),
+            })
     }
 }
@@ -0,0 +1,184 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraPersonalInfoDocument, OuraRingConfigurationDocument};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PersonMetadata {
+    pub age: Option<i32>,
+    pub weight: Option<f32>,
+    pub height: Option<f32>,
+    pub biological_sex: Option<String>,
+    pub ring_hardware_type: Option<String>,
+    pub ring_color: Option<String>,
+    pub ring_firmware_version: Option<String>,
+    pub ring_size: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl PersonMetadata {
+    pub fn from_documents(
+        personal_info: &OuraPersonalInfoDocument,
+        ring_configuration: Option<&OuraRingConfigurationDocument>,
+        timestamp: DateTime<Utc>,
+        person_name: &str,
+    ) -> PersonMetadata {
+        PersonMetadata {
+            age: personal_info.age,
+            weight: personal_info.weight,
+            height: personal_info.height,
+            biological_sex: personal_info.biological_sex.clone(),
+            ring_hardware_type: ring_configuration.and_then(|ring| ring.hardware_type.clone()),
+            ring_color: ring_configuration.and_then(|ring| ring.color.clone()),
+            ring_firmware_version: ring_configuration
+                .and_then(|ring| ring.firmware_version.clone()),
+            ring_size: ring_configuration.and_then(|ring| ring.size),
+            timestamp,
+            person_name: person_name.to_owned(),
+        }
+    }
+
+    /// Returns the metadata that rarely changes as InfluxDB tags, leaving out the values that are
+    /// not known. Age, weight and height are exported as fields of the `person_metadata`
+    /// measurement instead, so that a birthday does not start a new series for every measurement.
+    pub fn to_tags(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("biological_sex", self.biological_sex.clone()),
+            ("ring_hardware_type", self.ring_hardware_type.clone()),
+            ("ring_color", self.ring_color.clone()),
+            ("ring_firmware_version", self.ring_firmware_version.clone()),
+            ("ring_size", self.ring_size.map(|size| size.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
+}
+
+/// A person can have set up several rings over time, the most recently set up one is the one in use.
+fn latest_ring_configuration(
+    ring_configurations: &[OuraRingConfigurationDocument],
+) -> Option<&OuraRingConfigurationDocument> {
+    ring_configurations
+        .iter()
+        .max_by(|a, b| a.set_up_at.cmp(&b.set_up_at))
+}
+
+pub async fn poll_person_metadata(
+    person: &PollerPerson<'_>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling personal info and ring configuration for '{}'",
+        person.person.name
+    );
+
+    let personal_info = person.client.get_personal_info().await?;
+    let ring_configurations: Vec<OuraRingConfigurationDocument> = person
+        .client
+        .get_ring_configurations()
+        .try_collect()
+        .await?;
+
+    Ok(vec![OuraData::PersonMetadata(
+        PersonMetadata::from_documents(
+            &personal_info,
+            latest_ring_configuration(&ring_configurations),
+            Utc::now(),
+            &person.person.name,
+        ),
+    )])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{latest_ring_configuration, PersonMetadata};
+    use crate::oura_api::{OuraPersonalInfoDocument, OuraRingConfigurationDocument};
+    use chrono::{DateTime, Utc};
+
+    fn timestamp() -> DateTime<Utc> {
+        "2021-01-01T00:00:00+00:00"
+            .parse::<DateTime<Utc>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_latest_ring_configuration() {
+        let ring_configurations = vec![
+            OuraRingConfigurationDocument {
+                id: "old_ring".to_owned(),
+                set_up_at: Some("2021-01-01T12:00:00+00:00".to_owned()),
+                ..Default::default()
+            },
+            OuraRingConfigurationDocument {
+                id: "new_ring".to_owned(),
+                set_up_at: Some("2023-01-01T12:00:00+00:00".to_owned()),
+                ..Default::default()
+            },
+        ];
+
+        let latest = latest_ring_configuration(&ring_configurations).unwrap();
+
+        assert_eq!(latest.id, "new_ring");
+        assert!(latest_ring_configuration(&[]).is_none());
+    }
+
+    #[test]
+    fn test_person_metadata_to_tags() {
+        let personal_info = OuraPersonalInfoDocument {
+            id: "personal_info_id".to_owned(),
+            age: Some(34),
+            weight: Some(72.5),
+            height: None,
+            biological_sex: Some("female".to_owned()),
+            email: None,
+        };
+        let ring_configuration = OuraRingConfigurationDocument {
+            id: "ring_id".to_owned(),
+            hardware_type: Some("gen3".to_owned()),
+            firmware_version: Some("2.9.15".to_owned()),
+            color: Some(String::new()),
+            size: Some(9),
+            ..Default::default()
+        };
+
+        let metadata = PersonMetadata::from_documents(
+            &personal_info,
+            Some(&ring_configuration),
+            timestamp(),
+            "test_person",
+        );
+
+        assert_eq!(metadata.person_name, "test_person");
+        assert_eq!(metadata.age, Some(34));
+        assert_eq!(metadata.weight, Some(72.5));
+        assert_eq!(
+            metadata.to_tags(),
+            vec![
+                ("biological_sex", "female".to_owned()),
+                ("ring_hardware_type", "gen3".to_owned()),
+                ("ring_firmware_version", "2.9.15".to_owned()),
+                ("ring_size", "9".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_person_metadata_without_ring_configuration() {
+        let personal_info = OuraPersonalInfoDocument {
+            age: Some(34),
+            biological_sex: Some("male".to_owned()),
+            ..Default::default()
+        };
+
+        let metadata =
+            PersonMetadata::from_documents(&personal_info, None, timestamp(), "test_person");
+
+        assert_eq!(
+            metadata.to_tags(),
+            vec![("biological_sex", "male".to_owned())]
+        );
+    }
+}
@@ -1,21 +1,26 @@
-use super::{dates::TryOuraTimeStringParsing, errors::OuraPollingError};
-use crate::oura_api::OuraSleepDocument;
-use chrono::{DateTime, Utc};
+use super::{dates::TryOuraTimeStringParsing, errors::OuraPollingError, OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailyReadinessDocument, OuraSleepDocument};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use futures::TryStreamExt;
+use log::{debug, info};
+use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct Contributors {
-    pub activity_balance: u8,
-    pub body_temperature: u8,
-    pub hrv_balance: u8,
-    pub previous_day_activity: u8,
-    pub previous_night: u8,
-    pub recovery_index: u8,
-    pub resting_heart_rate: u8,
-    pub sleep_balance: u8,
+    pub activity_balance: Option<u8>,
+    pub body_temperature: Option<u8>,
+    pub hrv_balance: Option<u8>,
+    pub previous_day_activity: Option<u8>,
+    pub previous_night: Option<u8>,
+    pub recovery_index: Option<u8>,
+    pub resting_heart_rate: Option<u8>,
+    pub sleep_balance: Option<u8>,
 }
 
 #[derive(Debug)]
 pub struct Readiness {
+    pub id: String,
+    pub day: NaiveDate,
     pub score: u8,
     pub temperature_deviation: Option<f32>,
     pub temperature_trend_deviation: Option<f32>,
@@ -24,16 +29,44 @@ pub struct Readiness {
     pub person_name: String,
 }
 
+impl OuraDailyReadinessDocument {
+    pub fn try_to_readiness(&self, person: &str) -> Result<Readiness, OuraPollingError> {
+        let score = self
+            .score
+            .ok_or(OuraPollingError::NoDailyReadinessScoreFoundError {
+                readiness_id: self.id.to_string(),
+            })?;
+
+        Ok(Readiness {
+            id: self.id.to_string(),
+            day: self.day.try_parse_oura_date()?,
+            score,
+            temperature_deviation: self.temperature_deviation,
+            temperature_trend_deviation: self.temperature_trend_deviation,
+            contributors: Contributors {
+                activity_balance: self.contributors.activity_balance,
+                body_temperature: self.contributors.body_temperature,
+                hrv_balance: self.contributors.hrv_balance,
+                previous_day_activity: self.contributors.previous_day_activity,
+                previous_night: self.contributors.previous_night,
+                recovery_index: self.contributors.recovery_index,
+                resting_heart_rate: self.contributors.resting_heart_rate,
+                sleep_balance: self.contributors.sleep_balance,
+            },
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person.to_string(),
+        })
+    }
+}
+
 impl OuraSleepDocument {
     pub fn try_to_readiness(&self, person: &str) -> Result<Readiness, OuraPollingError> {
-        let timestamp = self.day.try_parse_oura_date()?;
+        let day = self.day.try_parse_oura_date()?;
 
         match &self.readiness {
-            None => {
-                return Err(OuraPollingError::NoReadinessDataFoundError {
-                    sleep_id: self.id.to_string(),
-                });
-            }
+            None => Err(OuraPollingError::NoReadinessDataFoundError {
+                sleep_id: self.id.to_string(),
+            }),
             Some(readiness) => {
                 let score = match readiness.score {
                     Some(score) => score,
@@ -44,28 +77,23 @@ impl OuraSleepDocument {
                     }
                 };
 
-                let timestamp = timestamp
-                    .and_hms_opt(0, 0, 0)
-                    .ok_or(OuraPollingError::UnexpectedError(String::from(
-                        "Cannot construct a NaiveDate from parsed oura date",
-                    )))?
-                    .and_utc();
-
                 Ok(Readiness {
+                    id: self.id.to_string(),
+                    day,
                     score,
                     temperature_deviation: readiness.temperature_deviation,
                     temperature_trend_deviation: readiness.temperature_trend_deviation,
                     contributors: Contributors {
-                        activity_balance: readiness.contributors.activity_balance,
-                        body_temperature: readiness.contributors.body_temperature,
-                        hrv_balance: readiness.contributors.hrv_balance,
-                        previous_day_activity: readiness.contributors.previous_day_activity,
-                        previous_night: readiness.contributors.previous_night,
-                        recovery_index: readiness.contributors.recovery_index,
-                        resting_heart_rate: readiness.contributors.resting_heart_rate,
-                        sleep_balance: readiness.contributors.sleep_balance,
+                        activity_balance: Some(readiness.contributors.activity_balance),
+                        body_temperature: Some(readiness.contributors.body_temperature),
+                        hrv_balance: Some(readiness.contributors.hrv_balance),
+                        previous_day_activity: Some(readiness.contributors.previous_day_activity),
+                        previous_night: Some(readiness.contributors.previous_night),
+                        recovery_index: Some(readiness.contributors.recovery_index),
+                        resting_heart_rate: Some(readiness.contributors.resting_heart_rate),
+                        sleep_balance: Some(readiness.contributors.sleep_balance),
                     },
-                    timestamp,
+                    timestamp: self.day.try_parse_oura_date_as_timestamp()?,
                     person_name: person.to_string(),
                 })
             }
@@ -73,10 +101,164 @@ impl OuraSleepDocument {
     }
 }
 
+/// Only the days before the day of `end_time` are checked, because the `daily_readiness`
+/// document of the current day does not exist until the morning.
+fn has_days_without_readiness(
+    readiness_days: &HashSet<NaiveDate>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> bool {
+    let end_day = end_time.date_naive();
+
+    start_time
+        .date_naive()
+        .iter_days()
+        .take_while(|day| day < &end_day)
+        .any(|day| !readiness_days.contains(&day))
+}
+
+/// Falls back to the readiness embedded in the sleep documents for the days that have no
+/// `daily_readiness` document. Only the first scored `long_sleep` document of a day is used,
+/// because naps and rest periods can carry a readiness score of their own.
+async fn poll_sleep_readiness_fallback(
+    person: &PollerPerson<'_>,
+    readiness_days: &HashSet<NaiveDate>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    let sleep_documents: Vec<OuraSleepDocument> = person
+        .client
+        .get_sleep_documents(start_time, end_time)
+        .try_collect()
+        .await?;
+
+    let mut fallback_days = HashSet::new();
+    let readiness_data = sleep_documents
+        .iter()
+        .filter(|document| document.sleep_type == "long_sleep")
+        .filter(
+            |document| matches!(&document.readiness, Some(readiness) if readiness.score.is_some()),
+        )
+        .filter(|document| match document.day.try_parse_oura_date() {
+            Ok(day) => !readiness_days.contains(&day) && fallback_days.insert(day),
+            Err(_) => true,
+        })
+        .map(|document| {
+            debug!(
+                "Using readiness from sleep document '{}' for '{}' as a fallback",
+                document.id, person.person.name
+            );
+
+            match document.try_to_readiness(&person.person.name) {
+                Ok(readiness) => OuraData::Readiness(readiness),
+                Err(err) => OuraData::from(err),
+            }
+        })
+        .collect();
+
+    Ok(readiness_data)
+}
+
+fn collect_readiness_days(data: &[OuraData]) -> HashSet<NaiveDate> {
+    data.iter()
+        .filter_map(|data| match data {
+            OuraData::Readiness(readiness) => Some(readiness.day),
+            _ => None,
+        })
+        .collect()
+}
+
+pub async fn poll_readiness_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling readiness data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let readiness_documents: Vec<OuraDailyReadinessDocument> = person
+        .client
+        .get_daily_readiness(start_time, end_time)
+        .try_collect()
+        .await?;
+
+    let (scored_documents, unscored_documents): (Vec<_>, Vec<_>) = readiness_documents
+        .iter()
+        .partition(|document| document.score.is_some());
+
+    let mut readiness_data: Vec<OuraData> = scored_documents
+        .iter()
+        .map(
+            |document| match document.try_to_readiness(&person.person.name) {
+                Ok(readiness) => OuraData::Readiness(readiness),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .collect();
+
+    // A day only counts as covered when its document could be converted, so that a document
+    // without a score still falls back to the readiness of the sleep documents.
+    let readiness_days: HashSet<NaiveDate> = collect_readiness_days(&readiness_data);
+
+    let mut fallback_days = HashSet::new();
+    if has_days_without_readiness(&readiness_days, start_time, end_time) {
+        match poll_sleep_readiness_fallback(person, &readiness_days, start_time, end_time).await {
+            Ok(fallback_data) => {
+                fallback_days = collect_readiness_days(&fallback_data);
+                readiness_data.extend(fallback_data);
+            }
+            Err(err) => readiness_data.push(OuraData::from(err)),
+        }
+    }
+
+    for document in unscored_documents {
+        match document.day.try_parse_oura_date() {
+            Ok(day) if fallback_days.contains(&day) => debug!(
+                "Daily readiness document '{}' for '{}' has no score, using the sleep readiness",
+                document.id, person.person.name
+            ),
+            _ => readiness_data.push(match document.try_to_readiness(&person.person.name) {
+                Ok(readiness) => OuraData::Readiness(readiness),
+                Err(err) => OuraData::from(err),
+            }),
+        }
+    }
+
+    Ok(readiness_data)
+}
+
+/// Polls the readiness of a single day the same way as `poll_readiness_data`, so that the webhook
+/// notifications get the same fallback to the readiness of the sleep documents as the polling.
+pub async fn poll_day_readiness_data(
+    person: &PollerPerson<'_>,
+    day: NaiveDate,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    let start_time = day.and_time(NaiveTime::MIN).and_utc();
+    let end_time = start_time + Duration::days(1);
+
+    let readiness_data = poll_readiness_data(person, &start_time, &end_time).await?;
+
+    // The range ends at the start of the next day, whose readiness may already exist.
+    Ok(readiness_data
+        .into_iter()
+        .filter(|data| !matches!(data, OuraData::Readiness(readiness) if readiness.day != day))
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
-    use crate::oura_api::{OuraContributors, OuraReadiness, OuraSleepDocument};
-    use chrono::{DateTime, Utc};
+    use super::{has_days_without_readiness, poll_readiness_data};
+    use crate::config::{OuraApi, OuraPerson};
+    use crate::oura_api::{
+        OuraContributors, OuraDailyReadinessContributors, OuraDailyReadinessDocument,
+        OuraHttpClient, OuraReadiness, OuraSleepDocument,
+    };
+    use crate::pollers::{OuraData, PollerPerson};
+    use chrono::{DateTime, NaiveDate, Utc};
+    use mockito::{Matcher, Server};
+    use std::collections::HashSet;
 
     #[test]
     fn test_try_to_readiness() {
@@ -103,18 +285,20 @@ mod test {
 
         let readiness = sleep_document.try_to_readiness("test_person").unwrap();
 
+        assert_eq!(readiness.id, "test_id");
+        assert_eq!(readiness.day, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
         assert_eq!(readiness.score, 80);
         assert_eq!(readiness.temperature_deviation, Some(0.5));
         assert_eq!(readiness.temperature_trend_deviation, Some(0.1));
 
-        assert_eq!(readiness.contributors.activity_balance, 1);
-        assert_eq!(readiness.contributors.body_temperature, 2);
-        assert_eq!(readiness.contributors.hrv_balance, 3);
-        assert_eq!(readiness.contributors.previous_day_activity, 4);
-        assert_eq!(readiness.contributors.previous_night, 5);
-        assert_eq!(readiness.contributors.recovery_index, 6);
-        assert_eq!(readiness.contributors.resting_heart_rate, 7);
-        assert_eq!(readiness.contributors.sleep_balance, 8);
+        assert_eq!(readiness.contributors.activity_balance, Some(1));
+        assert_eq!(readiness.contributors.body_temperature, Some(2));
+        assert_eq!(readiness.contributors.hrv_balance, Some(3));
+        assert_eq!(readiness.contributors.previous_day_activity, Some(4));
+        assert_eq!(readiness.contributors.previous_night, Some(5));
+        assert_eq!(readiness.contributors.recovery_index, Some(6));
+        assert_eq!(readiness.contributors.resting_heart_rate, Some(7));
+        assert_eq!(readiness.contributors.sleep_balance, Some(8));
 
         assert_eq!(
             readiness.timestamp,
@@ -174,4 +358,297 @@ mod test {
             "No readiness score found for sleep document with id: 'test_id'"
         );
     }
+
+    #[test]
+    fn test_try_daily_readiness_document_to_readiness() {
+        let readiness_document = OuraDailyReadinessDocument {
+            id: "readiness_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            score: Some(75),
+            temperature_deviation: Some(-0.2),
+            temperature_trend_deviation: None,
+            contributors: OuraDailyReadinessContributors {
+                activity_balance: Some(1),
+                body_temperature: Some(2),
+                hrv_balance: None,
+                previous_day_activity: Some(4),
+                previous_night: Some(5),
+                recovery_index: Some(6),
+                resting_heart_rate: Some(7),
+                sleep_balance: None,
+            },
+            ..Default::default()
+        };
+
+        let readiness = readiness_document.try_to_readiness("test_person").unwrap();
+
+        assert_eq!(readiness.id, "readiness_id");
+        assert_eq!(readiness.day, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+        assert_eq!(readiness.score, 75);
+        assert_eq!(readiness.temperature_deviation, Some(-0.2));
+        assert_eq!(readiness.temperature_trend_deviation, None);
+
+        assert_eq!(readiness.contributors.activity_balance, Some(1));
+        assert_eq!(readiness.contributors.body_temperature, Some(2));
+        assert_eq!(readiness.contributors.hrv_balance, None);
+        assert_eq!(readiness.contributors.previous_day_activity, Some(4));
+        assert_eq!(readiness.contributors.previous_night, Some(5));
+        assert_eq!(readiness.contributors.recovery_index, Some(6));
+        assert_eq!(readiness.contributors.resting_heart_rate, Some(7));
+        assert_eq!(readiness.contributors.sleep_balance, None);
+
+        assert_eq!(
+            readiness.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(readiness.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_daily_readiness_document_to_readiness_with_empty_score() {
+        let readiness_document = OuraDailyReadinessDocument {
+            id: "readiness_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            score: None,
+            ..Default::default()
+        };
+
+        let result = readiness_document.try_to_readiness("test_person");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "No readiness score found for daily readiness document with id: 'readiness_id'"
+        );
+    }
+
+    #[test]
+    fn test_has_days_without_readiness() {
+        let start_time = "2021-01-01T12:00:00+00:00"
+            .parse::<DateTime<Utc>>()
+            .unwrap();
+        let end_time = "2021-01-02T08:00:00+00:00"
+            .parse::<DateTime<Utc>>()
+            .unwrap();
+
+        let all_days: HashSet<NaiveDate> = HashSet::from([
+            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 1, 2).unwrap(),
+        ]);
+        let first_day_only: HashSet<NaiveDate> =
+            HashSet::from([NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()]);
+        let last_day_only: HashSet<NaiveDate> =
+            HashSet::from([NaiveDate::from_ymd_opt(2021, 1, 2).unwrap()]);
+
+        assert!(!has_days_without_readiness(
+            &all_days,
+            &start_time,
+            &end_time
+        ));
+        assert!(!has_days_without_readiness(
+            &first_day_only,
+            &start_time,
+            &end_time
+        ));
+        assert!(has_days_without_readiness(
+            &last_day_only,
+            &start_time,
+            &end_time
+        ));
+    }
+
+    async fn poll_readiness_with_documents(
+        readiness_documents: &str,
+        sleep_documents: &str,
+        sleep_status: usize,
+        expected_sleep_requests: usize,
+    ) -> Vec<OuraData> {
+        let mut server = Server::new_async().await;
+        let daily_readiness = server
+            .mock("GET", "/v2/usercollection/daily_readiness")
+            .match_query(Matcher::Any)
+            .with_body(format!(
+                r#"{{"data": {}, "next_token": null}}"#,
+                readiness_documents
+            ))
+            .create_async()
+            .await;
+        let sleep = server
+            .mock("GET", "/v2/usercollection/sleep")
+            .match_query(Matcher::Any)
+            .with_status(sleep_status)
+            .with_body(format!(
+                r#"{{"data": {}, "next_token": null}}"#,
+                sleep_documents
+            ))
+            .expect(expected_sleep_requests)
+            .create_async()
+            .await;
+
+        let (host, port) = server.host_with_port().split_once(':').map_or_else(
+            || panic!("mockito server address should contain a port"),
+            |(host, port)| (host.to_string(), port.to_string()),
+        );
+        let config = Some(OuraApi {
+            url: Some(format!("http://{}", host)),
+            port: Some(port),
+            proxy: None,
+            verbose_logging: None,
+            sandbox: None,
+            timeout_seconds: None,
+            retry: None,
+        });
+        let oura_person = OuraPerson {
+            name: "person".to_string(),
+            access_token: Some("token".to_string()),
+            oauth2: None,
+        };
+        let person = PollerPerson {
+            person: &oura_person,
+            client: OuraHttpClient::from_config(&config, &oura_person).unwrap(),
+        };
+        let start_time = "2021-01-01T12:00:00+00:00"
+            .parse::<DateTime<Utc>>()
+            .unwrap();
+        let end_time = "2021-01-02T08:00:00+00:00"
+            .parse::<DateTime<Utc>>()
+            .unwrap();
+
+        let data = poll_readiness_data(&person, &start_time, &end_time)
+            .await
+            .unwrap();
+
+        daily_readiness.assert_async().await;
+        sleep.assert_async().await;
+
+        data
+    }
+
+    #[tokio::test]
+    async fn test_poll_readiness_data_without_readiness_for_today() {
+        let data = poll_readiness_with_documents(
+            r#"[{
+                "id": "readiness_id",
+                "contributors": {},
+                "day": "2021-01-01",
+                "score": 80,
+                "temperature_deviation": null,
+                "temperature_trend_deviation": null,
+                "timestamp": "2021-01-01T00:00:00+00:00"
+            }]"#,
+            "[]",
+            200,
+            0,
+        )
+        .await;
+
+        assert_eq!(data.len(), 1);
+        assert!(matches!(&data[0], OuraData::Readiness(readiness) if readiness.score == 80));
+    }
+
+    fn sleep_document(id: &str, sleep_type: &str, score: Option<u8>) -> String {
+        format!(
+            r#"{{
+                "id": "{}",
+                "awake_time": 0,
+                "bedtime_end": "2021-01-01T07:00:00+00:00",
+                "bedtime_start": "2020-12-31T23:00:00+00:00",
+                "day": "2021-01-01",
+                "hrv": {{"interval": 300.0, "items": [], "timestamp": "2020-12-31T23:00:00+00:00"}},
+                "low_battery_alert": false,
+                "movement_30_sec": "",
+                "period": 0,
+                "readiness": {{
+                    "contributors": {{
+                        "activity_balance": 1,
+                        "body_temperature": 2,
+                        "hrv_balance": 3,
+                        "previous_day_activity": 4,
+                        "previous_night": 5,
+                        "recovery_index": 6,
+                        "resting_heart_rate": 7,
+                        "sleep_balance": 8
+                    }},
+                    "score": {},
+                    "temperature_deviation": null,
+                    "temperature_trend_deviation": null
+                }},
+                "time_in_bed": 28800,
+                "type": "{}"
+            }}"#,
+            id,
+            score.map_or("null".to_string(), |score| score.to_string()),
+            sleep_type
+        )
+    }
+
+    const UNSCORED_READINESS_DOCUMENT: &str = r#"[{
+        "id": "readiness_id",
+        "contributors": {},
+        "day": "2021-01-01",
+        "score": null,
+        "temperature_deviation": null,
+        "temperature_trend_deviation": null,
+        "timestamp": "2021-01-01T00:00:00+00:00"
+    }]"#;
+
+    #[tokio::test]
+    async fn test_poll_readiness_data_falls_back_when_score_is_missing() {
+        let data = poll_readiness_with_documents(
+            UNSCORED_READINESS_DOCUMENT,
+            &format!(
+                "[{}, {}, {}, {}]",
+                sleep_document("unscored_nap_id", "sleep", None),
+                sleep_document("nap_id", "sleep", Some(60)),
+                sleep_document("sleep_id", "long_sleep", Some(75)),
+                sleep_document("second_sleep_id", "long_sleep", Some(70))
+            ),
+            200,
+            1,
+        )
+        .await;
+
+        // The unscored daily readiness document is covered by the fallback, so it is not
+        // reported as an error.
+        assert_eq!(data.len(), 1);
+        assert!(matches!(&data[0], OuraData::Readiness(readiness) if readiness.id == "sleep_id"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_readiness_data_without_fallback_readiness() {
+        let data = poll_readiness_with_documents(
+            UNSCORED_READINESS_DOCUMENT,
+            &format!("[{}]", sleep_document("nap_id", "sleep", Some(60))),
+            200,
+            1,
+        )
+        .await;
+
+        assert_eq!(data.len(), 1);
+        assert!(matches!(&data[0], OuraData::Error { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_poll_readiness_data_keeps_readiness_when_fallback_fails() {
+        let data = poll_readiness_with_documents(
+            r#"[{
+                "id": "readiness_id",
+                "contributors": {},
+                "day": "2020-12-31",
+                "score": 80,
+                "temperature_deviation": null,
+                "temperature_trend_deviation": null,
+                "timestamp": "2020-12-31T00:00:00+00:00"
+            }]"#,
+            "[]",
+            400,
+            1,
+        )
+        .await;
+
+        assert_eq!(data.len(), 2);
+        assert!(matches!(&data[0], OuraData::Readiness(readiness) if readiness.score == 80));
+        assert!(matches!(&data[1], OuraData::Error { .. }));
+    }
 }
@@ -0,0 +1,170 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailyResilienceDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum ResilienceLevel {
+    Limited,
+    Adequate,
+    Solid,
+    Strong,
+    Exceptional,
+}
+
+impl FromStr for ResilienceLevel {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "limited" => Ok(ResilienceLevel::Limited),
+            "adequate" => Ok(ResilienceLevel::Adequate),
+            "solid" => Ok(ResilienceLevel::Solid),
+            "strong" => Ok(ResilienceLevel::Strong),
+            "exceptional" => Ok(ResilienceLevel::Exceptional),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "ResilienceLevel".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ResilienceLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResilienceLevel::Limited => write!(f, "limited"),
+            ResilienceLevel::Adequate => write!(f, "adequate"),
+            ResilienceLevel::Solid => write!(f, "solid"),
+            ResilienceLevel::Strong => write!(f, "strong"),
+            ResilienceLevel::Exceptional => write!(f, "exceptional"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ResilienceContributors {
+    pub sleep_recovery: f32,
+    pub daytime_recovery: f32,
+    pub stress: f32,
+}
+
+#[derive(Debug)]
+pub struct Resilience {
+    pub id: String,
+    pub level: ResilienceLevel,
+    pub contributors: ResilienceContributors,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraDailyResilienceDocument {
+    pub fn try_to_resilience(&self, person_name: &str) -> Result<Resilience, OuraPollingError> {
+        Ok(Resilience {
+            id: self.id.clone(),
+            level: self.level.parse()?,
+            contributors: ResilienceContributors {
+                sleep_recovery: self.contributors.sleep_recovery,
+                daytime_recovery: self.contributors.daytime_recovery,
+                stress: self.contributors.stress,
+            },
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_resilience_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling resilience data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let resilience_data: Vec<OuraData> = person
+        .client
+        .get_daily_resilience(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_resilience(&person.person.name) {
+                Ok(resilience) => OuraData::Resilience(resilience),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(resilience_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ResilienceLevel;
+    use crate::oura_api::{OuraDailyResilienceDocument, OuraResilienceContributors};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_resilience_level_from_str() {
+        assert_eq!(
+            "limited".parse::<ResilienceLevel>().unwrap(),
+            ResilienceLevel::Limited
+        );
+        assert_eq!(
+            "adequate".parse::<ResilienceLevel>().unwrap(),
+            ResilienceLevel::Adequate
+        );
+        assert_eq!(
+            "solid".parse::<ResilienceLevel>().unwrap(),
+            ResilienceLevel::Solid
+        );
+        assert_eq!(
+            "strong".parse::<ResilienceLevel>().unwrap(),
+            ResilienceLevel::Strong
+        );
+        assert_eq!(
+            "exceptional".parse::<ResilienceLevel>().unwrap(),
+            ResilienceLevel::Exceptional
+        );
+
+        let error = "weak".parse::<ResilienceLevel>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown ResilienceLevel: 'weak'");
+    }
+
+    #[test]
+    fn test_try_to_resilience() {
+        let resilience_document = OuraDailyResilienceDocument {
+            id: "resilience_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            level: "solid".to_owned(),
+            contributors: OuraResilienceContributors {
+                sleep_recovery: 70.5,
+                daytime_recovery: 40.0,
+                stress: 55.2,
+            },
+        };
+
+        let resilience = resilience_document
+            .try_to_resilience("test_person")
+            .unwrap();
+
+        assert_eq!(resilience.id, "resilience_id");
+        assert_eq!(resilience.level, ResilienceLevel::Solid);
+        assert_eq!(resilience.contributors.sleep_recovery, 70.5);
+        assert_eq!(resilience.contributors.daytime_recovery, 40.0);
+        assert_eq!(resilience.contributors.stress, 55.2);
+        assert_eq!(
+            resilience.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(resilience.person_name, "test_person");
+    }
+}
@@ -0,0 +1,177 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraRestModePeriodDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug)]
+pub struct RestModeEpisode {
+    pub tags: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct RestModePeriod {
+    pub id: String,
+    pub start_day: DateTime<Utc>,
+    pub end_day: Option<DateTime<Utc>>,
+    pub start_datetime: DateTime<Utc>,
+    pub end_datetime: Option<DateTime<Utc>>,
+    pub episodes: Vec<RestModeEpisode>,
+    pub person_name: String,
+}
+
+impl RestModePeriod {
+    /// A period without an end is still ongoing.
+    pub fn is_ongoing(&self) -> bool {
+        self.end_datetime.is_none()
+    }
+
+    pub fn duration_in_seconds(&self) -> Option<i64> {
+        self.end_datetime
+            .map(|end_datetime| (end_datetime - self.start_datetime).num_seconds())
+    }
+}
+
+impl OuraRestModePeriodDocument {
+    pub fn try_to_rest_mode_period(
+        &self,
+        person_name: &str,
+    ) -> Result<RestModePeriod, OuraPollingError> {
+        let start_day = self.start_day.try_parse_oura_date_as_timestamp()?;
+        let end_day = match &self.end_day {
+            Some(end_day) => Some(end_day.try_parse_oura_date_as_timestamp()?),
+            None => None,
+        };
+
+        // Exact times are optional in the API, fall back to the day boundaries.
+        let start_datetime = match &self.start_time {
+            Some(start_time) => start_time.try_parse_oura_timestamp()?,
+            None => start_day,
+        };
+        let end_datetime = match &self.end_time {
+            Some(end_time) => Some(end_time.try_parse_oura_timestamp()?),
+            None => end_day,
+        };
+
+        let episodes = self
+            .episodes
+            .iter()
+            .map(|episode| {
+                Ok(RestModeEpisode {
+                    tags: episode.tags.clone(),
+                    timestamp: episode.timestamp.try_parse_oura_timestamp()?,
+                })
+            })
+            .collect::<Result<Vec<RestModeEpisode>, OuraPollingError>>()?;
+
+        Ok(RestModePeriod {
+            id: self.id.clone(),
+            start_day,
+            end_day,
+            start_datetime,
+            end_datetime,
+            episodes,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_rest_mode_period_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling rest mode period data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let rest_mode_period_data: Vec<OuraData> = person
+        .client
+        .get_rest_mode_periods(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_rest_mode_period(&person.person.name) {
+                Ok(rest_mode_period) => OuraData::RestModePeriod(rest_mode_period),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(rest_mode_period_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::{OuraRestModeEpisode, OuraRestModePeriodDocument};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_rest_mode_period() {
+        let rest_mode_period_document = OuraRestModePeriodDocument {
+            id: "rest_mode_id".to_owned(),
+            start_day: "2021-01-01".to_owned(),
+            start_time: Some("2021-01-01T10:00:00+02:00".to_owned()),
+            end_day: Some("2021-01-03".to_owned()),
+            end_time: Some("2021-01-03T10:00:00+02:00".to_owned()),
+            episodes: vec![OuraRestModeEpisode {
+                tags: vec!["tag_generic_fatigue".to_owned()],
+                timestamp: "2021-01-01T10:00:00+02:00".to_owned(),
+            }],
+        };
+
+        let rest_mode_period = rest_mode_period_document
+            .try_to_rest_mode_period("test_person")
+            .unwrap();
+
+        assert_eq!(rest_mode_period.id, "rest_mode_id");
+        assert_eq!(
+            rest_mode_period.start_day,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            rest_mode_period.end_day,
+            Some(
+                "2021-01-03T00:00:00+00:00"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            rest_mode_period.start_datetime,
+            "2021-01-01T08:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert!(!rest_mode_period.is_ongoing());
+        assert_eq!(rest_mode_period.duration_in_seconds(), Some(172800));
+        assert_eq!(rest_mode_period.episodes.len(), 1);
+        assert_eq!(
+            rest_mode_period.episodes[0].tags,
+            vec!["tag_generic_fatigue"]
+        );
+        assert_eq!(rest_mode_period.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_rest_mode_period_ongoing() {
+        let rest_mode_period_document = OuraRestModePeriodDocument {
+            id: "rest_mode_id".to_owned(),
+            start_day: "2021-01-01".to_owned(),
+            ..Default::default()
+        };
+
+        let rest_mode_period = rest_mode_period_document
+            .try_to_rest_mode_period("test_person")
+            .unwrap();
+
+        assert_eq!(rest_mode_period.start_datetime, rest_mode_period.start_day);
+        assert!(rest_mode_period.is_ongoing());
+        assert_eq!(rest_mode_period.duration_in_seconds(), None);
+    }
+}
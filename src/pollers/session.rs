@@ -0,0 +1,309 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraSessionDocument, OuraSleepMeasurement};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionType {
+    Breathing,
+    Meditation,
+    Nap,
+    Relaxation,
+    Rest,
+    BodyStatus,
+}
+
+impl FromStr for SessionType {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "breathing" => Ok(SessionType::Breathing),
+            "meditation" => Ok(SessionType::Meditation),
+            "nap" => Ok(SessionType::Nap),
+            "relaxation" => Ok(SessionType::Relaxation),
+            "rest" => Ok(SessionType::Rest),
+            "body_status" => Ok(SessionType::BodyStatus),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "SessionType".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionType::Breathing => write!(f, "breathing"),
+            SessionType::Meditation => write!(f, "meditation"),
+            SessionType::Nap => write!(f, "nap"),
+            SessionType::Relaxation => write!(f, "relaxation"),
+            SessionType::Rest => write!(f, "rest"),
+            SessionType::BodyStatus => write!(f, "body_status"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Session {
+    pub id: String,
+    pub session_type: SessionType,
+    pub mood: Option<String>,
+    pub start_datetime: DateTime<Utc>,
+    pub end_datetime: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl Session {
+    pub fn duration_in_seconds(&self) -> i64 {
+        (self.end_datetime - self.start_datetime).num_seconds()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionSampleType {
+    HeartRate,
+    HeartRateVariability,
+    MotionCount,
+}
+
+#[derive(Debug)]
+pub struct SessionSample {
+    pub session_id: String,
+    pub session_type: SessionType,
+    pub sample_type: SessionSampleType,
+    pub value: f32,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraSessionDocument {
+    pub fn try_to_session(&self, person_name: &str) -> Result<Session, OuraPollingError> {
+        Ok(Session {
+            id: self.id.clone(),
+            session_type: self.session_type.parse()?,
+            mood: self.mood.clone(),
+            start_datetime: self.start_datetime.try_parse_oura_timestamp()?,
+            end_datetime: self.end_datetime.try_parse_oura_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+
+    fn try_expand_samples(
+        &self,
+        measurement: &Option<OuraSleepMeasurement>,
+        sample_type: SessionSampleType,
+        person_name: &str,
+    ) -> Result<Vec<SessionSample>, OuraPollingError> {
+        let measurement = match measurement {
+            Some(measurement) => measurement,
+            None => return Ok(vec![]),
+        };
+
+        let session_type: SessionType = self.session_type.parse()?;
+        let interval_in_seconds = measurement.interval.round() as i64;
+        let mut timestamp = measurement.timestamp.try_parse_oura_timestamp()?;
+        let mut samples: Vec<SessionSample> = Vec::new();
+
+        for item in &measurement.items {
+            if let Some(value) = item {
+                samples.push(SessionSample {
+                    session_id: self.id.clone(),
+                    session_type: session_type.clone(),
+                    sample_type,
+                    value: *value,
+                    timestamp,
+                    person_name: person_name.to_owned(),
+                });
+            }
+
+            timestamp = timestamp.add(chrono::Duration::seconds(interval_in_seconds));
+        }
+
+        Ok(samples)
+    }
+
+    pub fn try_to_session_samples(
+        &self,
+        person_name: &str,
+    ) -> Result<Vec<SessionSample>, OuraPollingError> {
+        let heart_rate =
+            self.try_expand_samples(&self.heart_rate, SessionSampleType::HeartRate, person_name)?;
+        let heart_rate_variability = self.try_expand_samples(
+            &self.heart_rate_variability,
+            SessionSampleType::HeartRateVariability,
+            person_name,
+        )?;
+        let motion_count = self.try_expand_samples(
+            &self.motion_count,
+            SessionSampleType::MotionCount,
+            person_name,
+        )?;
+
+        Ok(heart_rate
+            .into_iter()
+            .chain(heart_rate_variability)
+            .chain(motion_count)
+            .collect())
+    }
+}
+
+pub(super) fn parse_session_data<'a>(
+    person_name: &'a str,
+    session_documents: &'a [OuraSessionDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    session_documents
+        .iter()
+        .map(|document| match document.try_to_session(person_name) {
+            Ok(session) => OuraData::Session(session),
+            Err(err) => OuraData::from(err),
+        })
+}
+
+pub(super) fn parse_session_sample_data<'a>(
+    person_name: &'a str,
+    session_documents: &'a [OuraSessionDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    session_documents.iter().flat_map(|document| {
+        document.try_to_session_samples(person_name).map_or_else(
+            |err| vec![OuraData::from(err)],
+            |samples| samples.into_iter().map(OuraData::SessionSample).collect(),
+        )
+    })
+}
+
+pub async fn poll_session_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling session data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let person_name = &person.person.name;
+    let session_documents: Vec<OuraSessionDocument> = person
+        .client
+        .get_sessions(start_time, end_time)
+        .try_collect()
+        .await?;
+
+    let session_data = parse_session_data(person_name, &session_documents);
+    let session_sample_data = parse_session_sample_data(person_name, &session_documents);
+
+    Ok(session_data.chain(session_sample_data).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SessionSampleType, SessionType};
+    use crate::oura_api::{OuraSessionDocument, OuraSleepMeasurement};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_session_type_from_str() {
+        assert_eq!(
+            "breathing".parse::<SessionType>().unwrap(),
+            SessionType::Breathing
+        );
+        assert_eq!(
+            "meditation".parse::<SessionType>().unwrap(),
+            SessionType::Meditation
+        );
+        assert_eq!("nap".parse::<SessionType>().unwrap(), SessionType::Nap);
+        assert_eq!(
+            "relaxation".parse::<SessionType>().unwrap(),
+            SessionType::Relaxation
+        );
+        assert_eq!("rest".parse::<SessionType>().unwrap(), SessionType::Rest);
+        assert_eq!(
+            "body_status".parse::<SessionType>().unwrap(),
+            SessionType::BodyStatus
+        );
+
+        let error = "yoga".parse::<SessionType>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown SessionType: 'yoga'");
+    }
+
+    #[test]
+    fn test_try_to_session() {
+        let session_document = OuraSessionDocument {
+            id: "session_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            start_datetime: "2021-01-01T12:00:00+00:00".to_owned(),
+            end_datetime: "2021-01-01T12:10:00+00:00".to_owned(),
+            session_type: "meditation".to_owned(),
+            mood: Some("good".to_owned()),
+            ..Default::default()
+        };
+
+        let session = session_document.try_to_session("test_person").unwrap();
+
+        assert_eq!(session.id, "session_id");
+        assert_eq!(session.session_type, SessionType::Meditation);
+        assert_eq!(session.mood, Some("good".to_owned()));
+        assert_eq!(session.duration_in_seconds(), 600);
+        assert_eq!(session.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_session_samples() {
+        let session_document = OuraSessionDocument {
+            id: "session_id".to_owned(),
+            session_type: "breathing".to_owned(),
+            heart_rate: Some(OuraSleepMeasurement {
+                interval: 5.0,
+                items: vec![Some(60.0), None, Some(58.0)],
+                timestamp: "2021-01-01T12:00:00+00:00".to_owned(),
+            }),
+            heart_rate_variability: Some(OuraSleepMeasurement {
+                interval: 5.0,
+                items: vec![Some(45.0)],
+                timestamp: "2021-01-01T12:00:00+00:00".to_owned(),
+            }),
+            motion_count: None,
+            ..Default::default()
+        };
+
+        let samples = session_document
+            .try_to_session_samples("test_person")
+            .unwrap();
+
+        assert_eq!(samples.len(), 3);
+
+        assert_eq!(samples[0].sample_type, SessionSampleType::HeartRate);
+        assert_eq!(samples[0].value, 60.0);
+        assert_eq!(samples[0].session_id, "session_id");
+        assert_eq!(samples[0].session_type, SessionType::Breathing);
+        assert_eq!(samples[0].person_name, "test_person");
+        assert_eq!(
+            samples[0].timestamp,
+            "2021-01-01T12:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+
+        assert_eq!(samples[1].sample_type, SessionSampleType::HeartRate);
+        assert_eq!(samples[1].value, 58.0);
+        assert_eq!(
+            samples[1].timestamp,
+            "2021-01-01T12:00:10+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+
+        assert_eq!(
+            samples[2].sample_type,
+            SessionSampleType::HeartRateVariability
+        );
+        assert_eq!(samples[2].value, 45.0);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::oura_api::{OuraApiError, OuraSleepDocument};
 use crate::pollers::dates::TryOuraTimeStringParsing;
 use crate::pollers::errors::OuraPollingError;
+use crate::pollers::sleep_architecture::analyze_sleep_architecture;
+use crate::pollers::sleep_phase::segment_sleep_phases;
 use crate::pollers::OuraData;
 use chrono::{DateTime, NaiveDate, Utc};
+use futures::TryStreamExt;
 use log::info;
 use std::fmt::Display;
 
@@ -104,24 +107,24 @@ impl OuraSleepDocument {
     }
 }
 
-fn parse_sleep_data<'a>(
+pub(super) fn parse_sleep_data<'a>(
     person_name: &'a str,
-    sleep_documents: &'a Vec<OuraSleepDocument>,
+    sleep_documents: &'a [OuraSleepDocument],
 ) -> impl Iterator<Item = OuraData> + 'a {
-    return sleep_documents
+    sleep_documents
         .iter()
         .map(|document| match document.try_to_sleep_data(person_name) {
             Ok(sleep) => OuraData::Sleep(sleep),
             Err(err) => OuraData::from(err),
-        });
+        })
 }
 
-fn parse_hrv_data<'a>(
+pub(super) fn parse_hrv_data<'a>(
     person_name: &'a str,
-    sleep_documents: &'a Vec<OuraSleepDocument>,
+    sleep_documents: &'a [OuraSleepDocument],
 ) -> impl Iterator<Item = OuraData> + 'a {
     sleep_documents.iter().flat_map(|document| {
-        return document
+        document
             .try_to_heart_rate_variability(person_name)
             .map_or_else(
                 |err| vec![OuraData::from(err)],
@@ -130,45 +133,87 @@ fn parse_hrv_data<'a>(
                         .map(OuraData::HeartRateVariability)
                         .collect()
                 },
-            );
+            )
     })
 }
 
-fn parse_heart_rate_data<'a>(
+pub(super) fn parse_heart_rate_data<'a>(
     person_name: &'a str,
-    sleep_documents: &'a Vec<OuraSleepDocument>,
+    sleep_documents: &'a [OuraSleepDocument],
 ) -> impl Iterator<Item = OuraData> + 'a {
     sleep_documents.iter().flat_map(|document| {
-        return document.try_to_heart_rate_data(person_name).map_or_else(
+        document.try_to_heart_rate_data(person_name).map_or_else(
             |err| vec![OuraData::from(err)],
             |heart_rates| heart_rates.into_iter().map(OuraData::HeartRate).collect(),
-        );
+        )
     })
 }
 
-fn parse_sleep_phase_data<'a>(
+/// The sleep phases of each document are parsed once and the segments and the sleep architecture
+/// are derived from them, so that a document without sleep phases, such as a short nap, is
+/// reported only once.
+pub(super) fn parse_sleep_phase_data<'a>(
     person_name: &'a str,
-    sleep_documents: &'a Vec<OuraSleepDocument>,
+    sleep_documents: &'a [OuraSleepDocument],
 ) -> impl Iterator<Item = OuraData> + 'a {
     sleep_documents.iter().flat_map(|document| {
-        return document.try_extract_sleep_phases(person_name).map_or_else(
-            |err| vec![OuraData::from(err)],
-            |sleep_phases| sleep_phases.into_iter().map(OuraData::SleepPhase).collect(),
-        );
+        match document.try_extract_sleep_phases(person_name) {
+            Ok(sleep_phases) => {
+                let sleep_phase_segments = segment_sleep_phases(&sleep_phases);
+                let sleep_architecture = analyze_sleep_architecture(&sleep_phases);
+
+                sleep_phases
+                    .into_iter()
+                    .map(OuraData::SleepPhase)
+                    .chain(
+                        sleep_phase_segments
+                            .into_iter()
+                            .map(OuraData::SleepPhaseSegment),
+                    )
+                    .chain(sleep_architecture.map(OuraData::SleepArchitecture))
+                    .collect()
+            }
+            Err(err) => vec![OuraData::from(err)],
+        }
     })
 }
 
-fn parse_readiness_data<'a>(
+pub(super) fn parse_sleep_series_summary_data<'a>(
     person_name: &'a str,
-    sleep_documents: &'a Vec<OuraSleepDocument>,
+    sleep_documents: &'a [OuraSleepDocument],
 ) -> impl Iterator<Item = OuraData> + 'a {
-    sleep_documents.iter().map(|document| {
-        return document.try_to_readiness(person_name).map_or_else(
-            |err| OuraData::from(err),
-            |readiness| OuraData::Readiness(readiness),
-        );
+    sleep_documents.iter().flat_map(|document| {
+        vec![
+            document.try_summarize_heart_rate(person_name),
+            document.try_summarize_heart_rate_variability(person_name),
+        ]
+        .into_iter()
+        .filter_map(|summary| match summary {
+            Ok(summary) => summary.map(OuraData::SleepSeriesSummary),
+            Err(err) => Some(OuraData::from(err)),
+        })
     })
 }
+
+pub(super) fn parse_sleep_movement_data<'a>(
+    person_name: &'a str,
+    sleep_documents: &'a [OuraSleepDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    sleep_documents.iter().flat_map(|document| {
+        document
+            .try_extract_sleep_movements(person_name)
+            .map_or_else(
+                |err| vec![OuraData::from(err)],
+                |sleep_movements| {
+                    sleep_movements
+                        .into_iter()
+                        .map(OuraData::SleepMovement)
+                        .collect()
+                },
+            )
+    })
+}
+
 pub async fn poll_sleep_data<'a>(
     poller_person: &PollerPerson<'a>,
     start_time: &'a DateTime<Utc>,
@@ -180,24 +225,76 @@ pub async fn poll_sleep_data<'a>(
     );
 
     let person_name = &poller_person.person.name;
-    let response = poller_person
+    let sleep_documents: Vec<OuraSleepDocument> = poller_person
         .client
         .get_sleep_documents(start_time, end_time)
-        .await;
-    let sleep_documents = response?.data;
+        .try_collect()
+        .await?;
 
     let heart_rate_data = parse_heart_rate_data(person_name, &sleep_documents);
     let hrv_data = parse_hrv_data(person_name, &sleep_documents);
     let sleep_data = parse_sleep_data(person_name, &sleep_documents);
     let sleep_phase_data = parse_sleep_phase_data(person_name, &sleep_documents);
-    let readiness_data = parse_readiness_data(person_name, &sleep_documents);
+    let sleep_series_summary_data = parse_sleep_series_summary_data(person_name, &sleep_documents);
+    let sleep_movement_data = parse_sleep_movement_data(person_name, &sleep_documents);
 
     let oura_data = heart_rate_data
         .chain(hrv_data)
         .chain(sleep_data)
         .chain(sleep_phase_data)
-        .chain(readiness_data)
+        .chain(sleep_series_summary_data)
+        .chain(sleep_movement_data)
         .collect();
 
-    return Ok(oura_data);
+    Ok(oura_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_sleep_phase_data;
+    use crate::oura_api::OuraSleepDocument;
+    use crate::pollers::OuraData;
+
+    #[test]
+    fn test_parse_sleep_phase_data() {
+        let sleep_documents = vec![OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            sleep_phase_5_min: Some("4412".to_owned()),
+            ..Default::default()
+        }];
+
+        let data: Vec<OuraData> = parse_sleep_phase_data("person", &sleep_documents).collect();
+
+        let sleep_phases = data
+            .iter()
+            .filter(|data| matches!(data, OuraData::SleepPhase(_)))
+            .count();
+        let sleep_phase_segments = data
+            .iter()
+            .filter(|data| matches!(data, OuraData::SleepPhaseSegment(_)))
+            .count();
+        let sleep_architectures = data
+            .iter()
+            .filter(|data| matches!(data, OuraData::SleepArchitecture(_)))
+            .count();
+        assert_eq!(sleep_phases, 4);
+        assert_eq!(sleep_phase_segments, 3);
+        assert_eq!(sleep_architectures, 1);
+    }
+
+    #[test]
+    fn test_parse_sleep_phase_data_without_sleep_phases() {
+        let sleep_documents = vec![OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            sleep_phase_5_min: None,
+            ..Default::default()
+        }];
+
+        let data: Vec<OuraData> = parse_sleep_phase_data("person", &sleep_documents).collect();
+
+        assert_eq!(data.len(), 1);
+        assert!(matches!(data[0], OuraData::Error { .. }));
+    }
 }
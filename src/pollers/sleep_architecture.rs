@@ -0,0 +1,158 @@
+use crate::pollers::sleep_phase::{segment_sleep_phases, SleepPhase, SleepPhaseType};
+use chrono::{DateTime, Utc};
+
+/// Sleep architecture metrics of a night, derived from the 5 minute sleep phases. Durations are
+/// in seconds, and the latencies are counted from the sleep onset, which is the start of the
+/// first period that is not awake.
+#[derive(Debug)]
+pub struct SleepArchitecture {
+    pub sleep_id: String,
+    pub cycles: u32,
+    pub time_to_first_deep_sleep: Option<i64>,
+    pub time_to_first_rem_sleep: Option<i64>,
+    pub awakenings: u32,
+    pub wake_after_sleep_onset: i64,
+    /// The fraction of REM sleep in the first, second and last third of the sleep period.
+    pub rem_sleep_fractions: [f32; 3],
+    /// The fraction of deep sleep in the first, second and last third of the sleep period.
+    pub deep_sleep_fractions: [f32; 3],
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+fn is_asleep(sleep_phase: &SleepPhaseType) -> bool {
+    *sleep_phase != SleepPhaseType::Awake
+}
+
+fn fractions_per_third(sleep_phases: &[SleepPhase], phase_type: SleepPhaseType) -> [f32; 3] {
+    let mut totals = [0u32; 3];
+    let mut matches = [0u32; 3];
+
+    for (index, sleep_phase) in sleep_phases.iter().enumerate() {
+        let third = index * 3 / sleep_phases.len();
+        totals[third] += 1;
+
+        if sleep_phase.sleep_phase == phase_type {
+            matches[third] += 1;
+        }
+    }
+
+    let mut fractions = [0.0; 3];
+    for third in 0..3 {
+        if totals[third] > 0 {
+            fractions[third] = matches[third] as f32 / totals[third] as f32;
+        }
+    }
+
+    fractions
+}
+
+/// Returns `None` when the sleep phases contain no sleep at all.
+///
+/// A sleep cycle is counted each time a REM sleep period follows light or deep sleep, so the
+/// usual light, deep, light and REM sequence makes one cycle. Awakenings are the awake periods
+/// between the sleep onset and the final awakening.
+pub fn analyze_sleep_architecture(sleep_phases: &[SleepPhase]) -> Option<SleepArchitecture> {
+    let onset_index = sleep_phases
+        .iter()
+        .position(|sleep_phase| is_asleep(&sleep_phase.sleep_phase))?;
+    let final_index = sleep_phases
+        .iter()
+        .rposition(|sleep_phase| is_asleep(&sleep_phase.sleep_phase))?;
+    let sleep_period = &sleep_phases[onset_index..=final_index];
+    let sleep_onset = sleep_period[0].timestamp;
+
+    let time_to_first = |phase_type: SleepPhaseType| {
+        sleep_period
+            .iter()
+            .find(|sleep_phase| sleep_phase.sleep_phase == phase_type)
+            .map(|sleep_phase| (sleep_phase.timestamp - sleep_onset).num_seconds())
+    };
+
+    let mut cycles = 0;
+    let mut awakenings = 0;
+    let mut wake_after_sleep_onset = 0;
+    let mut non_rem_sleep_since_last_cycle = false;
+
+    for segment in segment_sleep_phases(sleep_period) {
+        match segment.sleep_phase {
+            SleepPhaseType::LightSleep | SleepPhaseType::DeepSleep => {
+                non_rem_sleep_since_last_cycle = true;
+            }
+            SleepPhaseType::REMSleep => {
+                if non_rem_sleep_since_last_cycle {
+                    cycles += 1;
+                    non_rem_sleep_since_last_cycle = false;
+                }
+            }
+            SleepPhaseType::Awake => {
+                awakenings += 1;
+                wake_after_sleep_onset += segment.duration_in_seconds();
+            }
+        }
+    }
+
+    Some(SleepArchitecture {
+        sleep_id: sleep_period[0].sleep_id.clone(),
+        cycles,
+        time_to_first_deep_sleep: time_to_first(SleepPhaseType::DeepSleep),
+        time_to_first_rem_sleep: time_to_first(SleepPhaseType::REMSleep),
+        awakenings,
+        wake_after_sleep_onset,
+        rem_sleep_fractions: fractions_per_third(sleep_period, SleepPhaseType::REMSleep),
+        deep_sleep_fractions: fractions_per_third(sleep_period, SleepPhaseType::DeepSleep),
+        timestamp: sleep_onset,
+        person_name: sleep_period[0].person_name.clone(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::analyze_sleep_architecture;
+    use crate::oura_api::OuraSleepDocument;
+    use chrono::DateTime;
+
+    #[test]
+    fn test_analyze_sleep_architecture() {
+        let oura_sleep_document = OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            sleep_phase_5_min: Some("44211234221133224".to_owned()),
+            ..Default::default()
+        };
+        let sleep_phases = oura_sleep_document
+            .try_extract_sleep_phases("person")
+            .unwrap();
+
+        let sleep_architecture = analyze_sleep_architecture(&sleep_phases).unwrap();
+
+        assert_eq!("id", sleep_architecture.sleep_id);
+        assert_eq!("person", sleep_architecture.person_name);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:10:00+03:00").unwrap(),
+            sleep_architecture.timestamp
+        );
+        assert_eq!(2, sleep_architecture.cycles);
+        assert_eq!(Some(300), sleep_architecture.time_to_first_deep_sleep);
+        assert_eq!(Some(1200), sleep_architecture.time_to_first_rem_sleep);
+        assert_eq!(1, sleep_architecture.awakenings);
+        assert_eq!(300, sleep_architecture.wake_after_sleep_onset);
+        assert_eq!([0.2, 0.0, 0.5], sleep_architecture.rem_sleep_fractions);
+        assert_eq!([0.4, 0.4, 0.0], sleep_architecture.deep_sleep_fractions);
+    }
+
+    #[test]
+    fn test_analyze_sleep_architecture_without_sleep() {
+        let oura_sleep_document = OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            sleep_phase_5_min: Some("444".to_owned()),
+            ..Default::default()
+        };
+        let sleep_phases = oura_sleep_document
+            .try_extract_sleep_phases("person")
+            .unwrap();
+
+        assert!(analyze_sleep_architecture(&sleep_phases).is_none());
+    }
+}
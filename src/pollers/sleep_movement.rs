@@ -0,0 +1,107 @@
+use crate::oura_api::OuraSleepDocument;
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Duration, Utc};
+use std::ops::Add;
+
+/// The movement of a 30 second period of a sleep, from 1 (no motion) to 4 (active).
+#[derive(Debug)]
+pub struct SleepMovement {
+    pub sleep_id: String,
+    pub movement: u8,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraSleepDocument {
+    pub fn try_extract_sleep_movements(
+        &self,
+        person_name: &str,
+    ) -> Result<Vec<SleepMovement>, OuraPollingError> {
+        let mut timestamp = self.bedtime_start.try_parse_oura_timestamp()?;
+        let mut sleep_movements: Vec<SleepMovement> = Vec::new();
+
+        for movement_char in self.movement_30_sec.chars() {
+            let movement = match movement_char.to_digit(10) {
+                Some(movement @ 1..=4) => movement as u8,
+                _ => {
+                    return Err(OuraPollingError::InvalidSleepMovementError {
+                        sleep_id: self.id.to_string(),
+                        movement: movement_char.to_string(),
+                    })
+                }
+            };
+
+            sleep_movements.push(SleepMovement {
+                sleep_id: self.id.clone(),
+                movement,
+                timestamp,
+                person_name: person_name.to_owned(),
+            });
+
+            timestamp = timestamp.add(Duration::seconds(30));
+        }
+
+        Ok(sleep_movements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_try_extract_sleep_movements() {
+        let oura_sleep_document = OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            movement_30_sec: "142".to_owned(),
+            ..Default::default()
+        };
+
+        let sleep_movements = oura_sleep_document
+            .try_extract_sleep_movements("person")
+            .unwrap();
+
+        assert_eq!(3, sleep_movements.len());
+
+        assert_eq!(1, sleep_movements[0].movement);
+        assert_eq!("person", sleep_movements[0].person_name);
+        assert_eq!("id", sleep_movements[0].sleep_id);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:00:00+03:00").unwrap(),
+            sleep_movements[0].timestamp
+        );
+
+        assert_eq!(4, sleep_movements[1].movement);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:00:30+03:00").unwrap(),
+            sleep_movements[1].timestamp
+        );
+
+        assert_eq!(2, sleep_movements[2].movement);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:01:00+03:00").unwrap(),
+            sleep_movements[2].timestamp
+        );
+    }
+
+    #[test]
+    fn test_try_extract_sleep_movements_with_invalid_movement() {
+        let oura_sleep_document = OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            movement_30_sec: "1x".to_owned(),
+            ..Default::default()
+        };
+
+        let error = oura_sleep_document
+            .try_extract_sleep_movements("person")
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid movement 'x' in sleep document with id: 'id'"
+        );
+    }
+}
@@ -4,6 +4,8 @@ use crate::pollers::errors::OuraPollingError;
 use chrono::{DateTime, Duration, Utc};
 use std::ops::Add;
 
+const SLEEP_PHASE_INTERVAL_IN_MINUTES: i64 = 5;
+
 #[derive(Debug)]
 pub struct SleepPhase {
     pub sleep_id: String,
@@ -12,7 +14,7 @@ pub struct SleepPhase {
     pub person_name: String,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SleepPhaseType {
     DeepSleep,
     LightSleep,
@@ -20,6 +22,24 @@ pub enum SleepPhaseType {
     Awake,
 }
 
+/// A continuous period of the same sleep phase. The ordinal tells which period of that phase it
+/// is within the night, e.g. the second REM sleep period has the ordinal 2.
+#[derive(Debug)]
+pub struct SleepPhaseSegment {
+    pub sleep_id: String,
+    pub sleep_phase: SleepPhaseType,
+    pub start_datetime: DateTime<Utc>,
+    pub end_datetime: DateTime<Utc>,
+    pub ordinal: u32,
+    pub person_name: String,
+}
+
+impl SleepPhaseSegment {
+    pub fn duration_in_seconds(&self) -> i64 {
+        (self.end_datetime - self.start_datetime).num_seconds()
+    }
+}
+
 impl TryFrom<char> for SleepPhaseType {
     type Error = OuraPollingError;
 
@@ -55,18 +75,51 @@ impl OuraSleepDocument {
                         person_name: person_name.to_owned(),
                     });
 
-                    timestamp = timestamp.add(Duration::minutes(5));
+                    timestamp = timestamp.add(Duration::minutes(SLEEP_PHASE_INTERVAL_IN_MINUTES));
                 }
 
-                return Ok(sleep_phases);
+                Ok(sleep_phases)
+            }
+            None => Err(OuraPollingError::NoSleepPhaseDataFoundError {
+                sleep_id: self.id.to_string(),
+            }),
+        }
+    }
+}
+
+/// Merges consecutive sleep phases of the same type into segments.
+pub fn segment_sleep_phases(sleep_phases: &[SleepPhase]) -> Vec<SleepPhaseSegment> {
+    let mut segments: Vec<SleepPhaseSegment> = Vec::new();
+
+    for sleep_phase in sleep_phases {
+        let end_datetime = sleep_phase
+            .timestamp
+            .add(Duration::minutes(SLEEP_PHASE_INTERVAL_IN_MINUTES));
+
+        match segments.last_mut() {
+            Some(segment) if segment.sleep_phase == sleep_phase.sleep_phase => {
+                segment.end_datetime = end_datetime;
             }
-            None => {
-                return Err(OuraPollingError::NoSleepPhaseDataFoundError {
-                    sleep_id: self.id.to_string(),
-                })
+            _ => {
+                let ordinal = segments
+                    .iter()
+                    .filter(|segment| segment.sleep_phase == sleep_phase.sleep_phase)
+                    .count() as u32
+                    + 1;
+
+                segments.push(SleepPhaseSegment {
+                    sleep_id: sleep_phase.sleep_id.clone(),
+                    sleep_phase: sleep_phase.sleep_phase,
+                    start_datetime: sleep_phase.timestamp,
+                    end_datetime,
+                    ordinal,
+                    person_name: sleep_phase.person_name.clone(),
+                });
             }
         }
     }
+
+    segments
 }
 
 #[cfg(test)]
@@ -128,4 +181,54 @@ mod test {
             sleep_phases[3].timestamp
         );
     }
+
+    #[test]
+    fn test_segment_sleep_phases() {
+        let oura_sleep_document = OuraSleepDocument {
+            id: "id".to_owned(),
+            bedtime_start: "2023-06-22T23:00:00+03:00".to_string(),
+            sleep_phase_5_min: Some("442233322".to_owned()),
+            ..Default::default()
+        };
+        let sleep_phases = oura_sleep_document
+            .try_extract_sleep_phases("person")
+            .unwrap();
+
+        let segments = segment_sleep_phases(&sleep_phases);
+
+        assert_eq!(4, segments.len());
+
+        assert_eq!(SleepPhaseType::Awake, segments[0].sleep_phase);
+        assert_eq!(1, segments[0].ordinal);
+        assert_eq!(600, segments[0].duration_in_seconds());
+        assert_eq!("person", segments[0].person_name);
+        assert_eq!("id", segments[0].sleep_id);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:00:00+03:00").unwrap(),
+            segments[0].start_datetime
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:10:00+03:00").unwrap(),
+            segments[0].end_datetime
+        );
+
+        assert_eq!(SleepPhaseType::LightSleep, segments[1].sleep_phase);
+        assert_eq!(1, segments[1].ordinal);
+        assert_eq!(600, segments[1].duration_in_seconds());
+
+        assert_eq!(SleepPhaseType::REMSleep, segments[2].sleep_phase);
+        assert_eq!(1, segments[2].ordinal);
+        assert_eq!(900, segments[2].duration_in_seconds());
+
+        assert_eq!(SleepPhaseType::LightSleep, segments[3].sleep_phase);
+        assert_eq!(2, segments[3].ordinal);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:35:00+03:00").unwrap(),
+            segments[3].start_datetime
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-06-22T23:45:00+03:00").unwrap(),
+            segments[3].end_datetime
+        );
+    }
 }
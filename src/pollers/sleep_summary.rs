@@ -0,0 +1,187 @@
+use crate::oura_api::{OuraSleepDocument, OuraSleepMeasurement};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SleepSeriesType {
+    HeartRate,
+    HeartRateVariability,
+}
+
+/// Summary statistics of the heart rate or HRV samples of a sleep. The nadir offset is the time
+/// in seconds from the bedtime start to the lowest sample.
+#[derive(Debug)]
+pub struct SleepSeriesSummary {
+    pub sleep_id: String,
+    pub series_type: SleepSeriesType,
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32,
+    pub median: f32,
+    pub p10: f32,
+    pub p90: f32,
+    pub standard_deviation: f32,
+    pub nadir_offset_in_seconds: i64,
+    pub samples: u32,
+    pub null_samples: u32,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted_values: &[f32], percentile: f32) -> f32 {
+    let rank = percentile / 100.0 * (sorted_values.len() - 1) as f32;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted_values[lower] + (sorted_values[upper] - sorted_values[lower]) * (rank - lower as f32)
+}
+
+impl OuraSleepDocument {
+    fn try_summarize(
+        &self,
+        measurement: &OuraSleepMeasurement,
+        series_type: SleepSeriesType,
+        person_name: &str,
+    ) -> Result<Option<SleepSeriesSummary>, OuraPollingError> {
+        let values: Vec<f32> = measurement.items.iter().flatten().copied().collect();
+        if values.is_empty() {
+            return Ok(None);
+        }
+
+        let bedtime_start = self.bedtime_start.try_parse_oura_timestamp()?;
+        let measurement_start = measurement.timestamp.try_parse_oura_timestamp()?;
+
+        // The first occurrence of the lowest sample is the nadir.
+        let (nadir_index, min) = measurement
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| item.map(|value| (index, value)))
+            .fold((0, f32::MAX), |nadir, (index, value)| {
+                if value < nadir.1 {
+                    (index, value)
+                } else {
+                    nadir
+                }
+            });
+        let nadir_offset_in_seconds = (measurement_start - bedtime_start).num_seconds()
+            + (nadir_index as f32 * measurement.interval).round() as i64;
+
+        let mut sorted_values = values.clone();
+        sorted_values.sort_by(|a, b| a.total_cmp(b));
+
+        let count = values.len() as f32;
+        let mean = values.iter().sum::<f32>() / count;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f32>()
+            / count;
+
+        Ok(Some(SleepSeriesSummary {
+            sleep_id: self.id.clone(),
+            series_type,
+            min,
+            max: sorted_values[sorted_values.len() - 1],
+            mean,
+            median: percentile(&sorted_values, 50.0),
+            p10: percentile(&sorted_values, 10.0),
+            p90: percentile(&sorted_values, 90.0),
+            standard_deviation: variance.sqrt(),
+            nadir_offset_in_seconds,
+            samples: values.len() as u32,
+            null_samples: (measurement.items.len() - values.len()) as u32,
+            timestamp: bedtime_start,
+            person_name: person_name.to_owned(),
+        }))
+    }
+
+    pub fn try_summarize_heart_rate(
+        &self,
+        person_name: &str,
+    ) -> Result<Option<SleepSeriesSummary>, OuraPollingError> {
+        match &self.heart_rate {
+            Some(heart_rate) => {
+                self.try_summarize(heart_rate, SleepSeriesType::HeartRate, person_name)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn try_summarize_heart_rate_variability(
+        &self,
+        person_name: &str,
+    ) -> Result<Option<SleepSeriesSummary>, OuraPollingError> {
+        self.try_summarize(
+            &self.hrv,
+            SleepSeriesType::HeartRateVariability,
+            person_name,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SleepSeriesType;
+    use crate::oura_api::{OuraSleepDocument, OuraSleepMeasurement};
+    use chrono::DateTime;
+
+    #[test]
+    fn test_try_summarize_heart_rate() {
+        let sleep_document = OuraSleepDocument {
+            id: "sleep_id".to_owned(),
+            bedtime_start: "2021-01-01T00:00:00+00:00".to_owned(),
+            heart_rate: Some(OuraSleepMeasurement {
+                interval: 300.0,
+                timestamp: "2021-01-01T00:05:00+00:00".to_owned(),
+                items: vec![Some(60.0), None, Some(50.0), Some(55.0), Some(65.0), None],
+            }),
+            ..Default::default()
+        };
+
+        let summary = sleep_document
+            .try_summarize_heart_rate("test_person")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(summary.sleep_id, "sleep_id");
+        assert_eq!(summary.series_type, SleepSeriesType::HeartRate);
+        assert_eq!(summary.person_name, "test_person");
+        assert_eq!(
+            summary.timestamp,
+            DateTime::parse_from_rfc3339("2021-01-01T00:00:00+00:00").unwrap()
+        );
+        assert_eq!(summary.min, 50.0);
+        assert_eq!(summary.max, 65.0);
+        assert_eq!(summary.mean, 57.5);
+        assert_eq!(summary.median, 57.5);
+        assert!((summary.p10 - 51.5).abs() < 0.001);
+        assert!((summary.p90 - 63.5).abs() < 0.001);
+        assert!((summary.standard_deviation - 5.5902).abs() < 0.001);
+        assert_eq!(summary.nadir_offset_in_seconds, 900);
+        assert_eq!(summary.samples, 4);
+        assert_eq!(summary.null_samples, 2);
+    }
+
+    #[test]
+    fn test_try_summarize_heart_rate_variability_without_samples() {
+        let sleep_document = OuraSleepDocument {
+            id: "sleep_id".to_owned(),
+            bedtime_start: "2021-01-01T00:00:00+00:00".to_owned(),
+            hrv: OuraSleepMeasurement {
+                interval: 300.0,
+                timestamp: "2021-01-01T00:00:00+00:00".to_owned(),
+                items: vec![None, None],
+            },
+            ..Default::default()
+        };
+
+        let summary = sleep_document
+            .try_summarize_heart_rate_variability("test_person")
+            .unwrap();
+
+        assert!(summary.is_none());
+    }
+}
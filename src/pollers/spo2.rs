@@ -0,0 +1,100 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailySpO2Document};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug)]
+pub struct SpO2 {
+    pub id: String,
+    pub average_spo2_percentage: Option<f32>,
+    pub breathing_disturbance_index: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraDailySpO2Document {
+    pub fn try_to_spo2(&self, person_name: &str) -> Result<SpO2, OuraPollingError> {
+        Ok(SpO2 {
+            id: self.id.clone(),
+            average_spo2_percentage: self
+                .spo2_percentage
+                .as_ref()
+                .and_then(|spo2_percentage| spo2_percentage.average),
+            breathing_disturbance_index: self.breathing_disturbance_index,
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_spo2_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling SpO2 data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let spo2_data: Vec<OuraData> = person
+        .client
+        .get_daily_spo2(start_time, end_time)
+        .map_ok(|document| match document.try_to_spo2(&person.person.name) {
+            Ok(spo2) => OuraData::SpO2(spo2),
+            Err(parsing_error) => OuraData::from(parsing_error),
+        })
+        .try_collect()
+        .await?;
+
+    Ok(spo2_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::{OuraDailySpO2Document, OuraSpO2Percentage};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_spo2() {
+        let spo2_document = OuraDailySpO2Document {
+            id: "spo2_id".to_owned(),
+            breathing_disturbance_index: Some(7),
+            day: "2021-01-01".to_owned(),
+            spo2_percentage: Some(OuraSpO2Percentage {
+                average: Some(96.5),
+            }),
+        };
+
+        let spo2 = spo2_document.try_to_spo2("test_person").unwrap();
+
+        assert_eq!(spo2.id, "spo2_id");
+        assert_eq!(spo2.average_spo2_percentage, Some(96.5));
+        assert_eq!(spo2.breathing_disturbance_index, Some(7));
+        assert_eq!(
+            spo2.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(spo2.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_spo2_without_measurements() {
+        let spo2_document = OuraDailySpO2Document {
+            id: "spo2_id".to_owned(),
+            breathing_disturbance_index: None,
+            day: "2021-01-01".to_owned(),
+            spo2_percentage: None,
+        };
+
+        let spo2 = spo2_document.try_to_spo2("test_person").unwrap();
+
+        assert_eq!(spo2.average_spo2_percentage, None);
+        assert_eq!(spo2.breathing_disturbance_index, None);
+    }
+}
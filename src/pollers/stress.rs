@@ -0,0 +1,161 @@
+use super::{dates::TryOuraTimeStringParsing, errors::OuraPollingError, OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraDailyStressDocument};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum StressDaySummary {
+    Restored,
+    Normal,
+    Stressful,
+}
+
+impl FromStr for StressDaySummary {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "restored" => Ok(StressDaySummary::Restored),
+            "normal" => Ok(StressDaySummary::Normal),
+            "stressful" => Ok(StressDaySummary::Stressful),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "StressDaySummary".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for StressDaySummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StressDaySummary::Restored => write!(f, "restored"),
+            StressDaySummary::Normal => write!(f, "normal"),
+            StressDaySummary::Stressful => write!(f, "stressful"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Stress {
+    pub id: String,
+    pub day_summary: Option<StressDaySummary>,
+    pub stress_high: Option<i32>,
+    pub recovery_high: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraDailyStressDocument {
+    pub fn try_to_stress(&self, person: &str) -> Result<Stress, OuraPollingError> {
+        let day_summary = match &self.day_summary {
+            Some(day_summary) => Some(day_summary.parse()?),
+            None => None,
+        };
+
+        Ok(Stress {
+            id: self.id.to_string(),
+            day_summary,
+            stress_high: self.stress_high,
+            recovery_high: self.recovery_high,
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person.to_string(),
+        })
+    }
+}
+
+pub async fn poll_stress_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling stress data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let stress_data: Vec<OuraData> = person
+        .client
+        .get_daily_stress(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_stress(&person.person.name) {
+                Ok(stress) => OuraData::Stress(stress),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(stress_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::StressDaySummary;
+    use crate::oura_api::OuraDailyStressDocument;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_stress_day_summary_from_str() {
+        assert_eq!(
+            "restored".parse::<StressDaySummary>().unwrap(),
+            StressDaySummary::Restored
+        );
+        assert_eq!(
+            "normal".parse::<StressDaySummary>().unwrap(),
+            StressDaySummary::Normal
+        );
+        assert_eq!(
+            "stressful".parse::<StressDaySummary>().unwrap(),
+            StressDaySummary::Stressful
+        );
+
+        let error = "relaxed".parse::<StressDaySummary>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown StressDaySummary: 'relaxed'");
+    }
+
+    #[test]
+    fn test_try_to_stress() {
+        let stress_document = OuraDailyStressDocument {
+            id: "stress_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            day_summary: Some("stressful".to_owned()),
+            recovery_high: Some(1800),
+            stress_high: Some(7200),
+        };
+
+        let stress = stress_document.try_to_stress("test_person").unwrap();
+
+        assert_eq!(stress.id, "stress_id");
+        assert_eq!(stress.day_summary, Some(StressDaySummary::Stressful));
+        assert_eq!(stress.recovery_high, Some(1800));
+        assert_eq!(stress.stress_high, Some(7200));
+        assert_eq!(
+            stress.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(stress.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_stress_without_day_summary() {
+        let stress_document = OuraDailyStressDocument {
+            id: "stress_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            day_summary: None,
+            recovery_high: None,
+            stress_high: None,
+        };
+
+        let stress = stress_document.try_to_stress("test_person").unwrap();
+
+        assert_eq!(stress.day_summary, None);
+        assert_eq!(stress.recovery_high, None);
+        assert_eq!(stress.stress_high, None);
+    }
+}
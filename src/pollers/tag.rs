@@ -0,0 +1,220 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraEnhancedTagDocument, OuraTagDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum TagSource {
+    Tag,
+    EnhancedTag,
+}
+
+impl fmt::Display for TagSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TagSource::Tag => write!(f, "tag"),
+            TagSource::EnhancedTag => write!(f, "enhanced_tag"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Tag {
+    pub id: String,
+    pub tag_type: String,
+    pub source: TagSource,
+    pub comment: Option<String>,
+    pub start_datetime: DateTime<Utc>,
+    pub end_datetime: Option<DateTime<Utc>>,
+    pub person_name: String,
+}
+
+impl Tag {
+    pub fn duration_in_seconds(&self) -> i64 {
+        match self.end_datetime {
+            Some(end_datetime) => (end_datetime - self.start_datetime).num_seconds(),
+            None => 0,
+        }
+    }
+}
+
+impl OuraTagDocument {
+    pub fn try_to_tags(&self, person_name: &str) -> Result<Vec<Tag>, OuraPollingError> {
+        let start_datetime = self.timestamp.try_parse_oura_timestamp()?;
+
+        Ok(self
+            .tags
+            .iter()
+            .map(|tag_type| Tag {
+                id: self.id.clone(),
+                tag_type: tag_type.clone(),
+                source: TagSource::Tag,
+                comment: self.text.clone(),
+                start_datetime,
+                end_datetime: None,
+                person_name: person_name.to_owned(),
+            })
+            .collect())
+    }
+}
+
+impl OuraEnhancedTagDocument {
+    pub fn try_to_tag(&self, person_name: &str) -> Result<Tag, OuraPollingError> {
+        let end_datetime = match &self.end_time {
+            Some(end_time) => Some(end_time.try_parse_oura_timestamp()?),
+            None => None,
+        };
+
+        // Custom tags all share the same type code, their name is what tells them apart.
+        let tag_type = self
+            .custom_name
+            .as_ref()
+            .or(self.tag_type_code.as_ref())
+            .map_or_else(|| "unknown".to_owned(), |tag_type| tag_type.clone());
+
+        Ok(Tag {
+            id: self.id.clone(),
+            tag_type,
+            source: TagSource::EnhancedTag,
+            comment: self.comment.clone(),
+            start_datetime: self.start_time.try_parse_oura_timestamp()?,
+            end_datetime,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub(super) fn parse_tag_data<'a>(
+    person_name: &'a str,
+    tag_documents: &'a [OuraTagDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    tag_documents.iter().flat_map(|document| {
+        document.try_to_tags(person_name).map_or_else(
+            |err| vec![OuraData::from(err)],
+            |tags| tags.into_iter().map(OuraData::Tag).collect(),
+        )
+    })
+}
+
+pub(super) fn parse_enhanced_tag_data<'a>(
+    person_name: &'a str,
+    enhanced_tag_documents: &'a [OuraEnhancedTagDocument],
+) -> impl Iterator<Item = OuraData> + 'a {
+    enhanced_tag_documents
+        .iter()
+        .map(|document| match document.try_to_tag(person_name) {
+            Ok(tag) => OuraData::Tag(tag),
+            Err(err) => OuraData::from(err),
+        })
+}
+
+pub async fn poll_tag_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling tag data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let person_name = &person.person.name;
+    let tag_documents: Vec<OuraTagDocument> = person
+        .client
+        .get_tags(start_time, end_time)
+        .try_collect()
+        .await?;
+    let enhanced_tag_documents: Vec<OuraEnhancedTagDocument> = person
+        .client
+        .get_enhanced_tags(start_time, end_time)
+        .try_collect()
+        .await?;
+
+    let tag_data = parse_tag_data(person_name, &tag_documents);
+    let enhanced_tag_data = parse_enhanced_tag_data(person_name, &enhanced_tag_documents);
+
+    Ok(tag_data.chain(enhanced_tag_data).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::TagSource;
+    use crate::oura_api::{OuraEnhancedTagDocument, OuraTagDocument};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_tags() {
+        let tag_document = OuraTagDocument {
+            id: "tag_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            text: Some("Late dinner".to_owned()),
+            timestamp: "2021-01-01T21:00:00+02:00".to_owned(),
+            tags: vec![
+                "tag_generic_alcohol".to_owned(),
+                "tag_generic_late_meal".to_owned(),
+            ],
+        };
+
+        let tags = tag_document.try_to_tags("test_person").unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].id, "tag_id");
+        assert_eq!(tags[0].tag_type, "tag_generic_alcohol");
+        assert_eq!(tags[0].source, TagSource::Tag);
+        assert_eq!(tags[0].comment, Some("Late dinner".to_owned()));
+        assert_eq!(
+            tags[0].start_datetime,
+            "2021-01-01T19:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(tags[0].end_datetime, None);
+        assert_eq!(tags[0].duration_in_seconds(), 0);
+        assert_eq!(tags[0].person_name, "test_person");
+        assert_eq!(tags[1].tag_type, "tag_generic_late_meal");
+    }
+
+    #[test]
+    fn test_try_to_tag_from_enhanced_tag() {
+        let enhanced_tag_document = OuraEnhancedTagDocument {
+            id: "enhanced_tag_id".to_owned(),
+            tag_type_code: Some("tag_generic_sick".to_owned()),
+            start_time: "2021-01-01T08:00:00+00:00".to_owned(),
+            end_time: Some("2021-01-02T08:00:00+00:00".to_owned()),
+            start_day: "2021-01-01".to_owned(),
+            end_day: Some("2021-01-02".to_owned()),
+            comment: Some("Flu".to_owned()),
+            custom_name: None,
+        };
+
+        let tag = enhanced_tag_document.try_to_tag("test_person").unwrap();
+
+        assert_eq!(tag.id, "enhanced_tag_id");
+        assert_eq!(tag.tag_type, "tag_generic_sick");
+        assert_eq!(tag.source, TagSource::EnhancedTag);
+        assert_eq!(tag.comment, Some("Flu".to_owned()));
+        assert_eq!(tag.duration_in_seconds(), 86400);
+        assert_eq!(tag.person_name, "test_person");
+    }
+
+    #[test]
+    fn test_try_to_tag_from_custom_enhanced_tag() {
+        let enhanced_tag_document = OuraEnhancedTagDocument {
+            id: "enhanced_tag_id".to_owned(),
+            tag_type_code: Some("custom".to_owned()),
+            start_time: "2021-01-01T08:00:00+00:00".to_owned(),
+            custom_name: Some("Espresso".to_owned()),
+            ..Default::default()
+        };
+
+        let tag = enhanced_tag_document.try_to_tag("test_person").unwrap();
+
+        assert_eq!(tag.tag_type, "Espresso");
+        assert_eq!(tag.end_datetime, None);
+    }
+}
@@ -0,0 +1,79 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraVO2MaxDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+
+#[derive(Debug)]
+pub struct VO2Max {
+    pub id: String,
+    pub vo2_max: Option<f32>,
+    pub timestamp: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl OuraVO2MaxDocument {
+    pub fn try_to_vo2_max(&self, person_name: &str) -> Result<VO2Max, OuraPollingError> {
+        Ok(VO2Max {
+            id: self.id.clone(),
+            vo2_max: self.vo2_max,
+            timestamp: self.day.try_parse_oura_date_as_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_vo2_max_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling VO2 max data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let vo2_max_data: Vec<OuraData> = person
+        .client
+        .get_vo2_max(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_vo2_max(&person.person.name) {
+                Ok(vo2_max) => OuraData::VO2Max(vo2_max),
+                Err(err) => OuraData::from(err),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(vo2_max_data)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::oura_api::OuraVO2MaxDocument;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_try_to_vo2_max() {
+        let vo2_max_document = OuraVO2MaxDocument {
+            id: "vo2_max_id".to_owned(),
+            day: "2021-01-01".to_owned(),
+            timestamp: "2021-01-01T08:00:00+00:00".to_owned(),
+            vo2_max: Some(42.5),
+        };
+
+        let vo2_max = vo2_max_document.try_to_vo2_max("test_person").unwrap();
+
+        assert_eq!(vo2_max.id, "vo2_max_id");
+        assert_eq!(vo2_max.vo2_max, Some(42.5));
+        assert_eq!(
+            vo2_max.timestamp,
+            "2021-01-01T00:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(vo2_max.person_name, "test_person");
+    }
+}
@@ -0,0 +1,263 @@
+use super::activity::{parse_activity_class_data, parse_activity_data, parse_met_data};
+use super::dates::TryOuraTimeStringParsing;
+use super::person_metadata::poll_person_metadata;
+use super::readiness::poll_day_readiness_data;
+use super::session::{parse_session_data, parse_session_sample_data};
+use super::sleep::{
+    parse_heart_rate_data, parse_hrv_data, parse_sleep_data, parse_sleep_movement_data,
+    parse_sleep_phase_data, parse_sleep_series_summary_data,
+};
+use super::tag::{parse_enhanced_tag_data, parse_tag_data};
+use super::{OuraData, Poller, PollerPerson};
+use crate::oura_api::{
+    OuraActivityDocument, OuraApiError, OuraDailyCardiovascularAgeDocument,
+    OuraDailyReadinessDocument, OuraDailyResilienceDocument, OuraDailySleepDocument,
+    OuraDailySpO2Document, OuraDailyStressDocument, OuraEnhancedTagDocument,
+    OuraRestModePeriodDocument, OuraSessionDocument, OuraSleepDocument, OuraSleepTimeDocument,
+    OuraTagDocument, OuraVO2MaxDocument, OuraWorkoutDocument,
+};
+use log::{info, warn};
+use std::collections::HashMap;
+
+/// The data types whose documents can be fetched when a webhook notification arrives.
+pub const WEBHOOK_DATA_TYPES: &[&str] = &[
+    "daily_activity",
+    "daily_cardiovascular_age",
+    "daily_readiness",
+    "daily_resilience",
+    "daily_sleep",
+    "daily_spo2",
+    "daily_stress",
+    "enhanced_tag",
+    "rest_mode_period",
+    "session",
+    "sleep",
+    "sleep_time",
+    "tag",
+    "vO2_max",
+    "workout",
+];
+
+impl Poller<'_> {
+    /// Webhook notifications identify the person only by the Oura user id, which is the id of the
+    /// personal info document.
+    pub async fn get_person_names_by_user_id(&self) -> HashMap<String, String> {
+        let mut person_names_by_user_id = HashMap::new();
+
+        for person in &self.persons {
+            match person.client.get_personal_info().await {
+                Ok(personal_info) => {
+                    person_names_by_user_id.insert(personal_info.id, person.person.name.clone());
+                }
+                Err(err) => warn!(
+                    "Cannot resolve the Oura user id of '{}': {}",
+                    person.person.name, err
+                ),
+            }
+        }
+
+        person_names_by_user_id
+    }
+
+    /// Polls the personal info and ring configuration of every person, when person metadata is
+    /// enabled. Webhook notifications do not cover them, so they are polled separately.
+    pub async fn poll_all_person_metadata(&self) -> Vec<OuraData> {
+        let mut person_metadata = Vec::new();
+
+        for person in self.persons.iter().filter(|_| self.poll_person_metadata) {
+            match poll_person_metadata(person).await {
+                Ok(data) => person_metadata.extend(data),
+                Err(err) => person_metadata.push(OuraData::from(err)),
+            }
+        }
+
+        person_metadata
+    }
+
+    pub async fn poll_webhook_document(
+        &self,
+        person_name: &str,
+        data_type: &str,
+        document_id: &str,
+    ) -> Vec<OuraData> {
+        let person = match self
+            .persons
+            .iter()
+            .find(|person| person.person.name == person_name)
+        {
+            Some(person) => person,
+            None => {
+                return vec![OuraData::Error {
+                    message: format!("Unknown person '{}'", person_name),
+                }]
+            }
+        };
+
+        poll_document(person, data_type, document_id)
+            .await
+            .unwrap_or_else(|err| vec![OuraData::from(err)])
+    }
+}
+
+async fn poll_document(
+    person: &PollerPerson<'_>,
+    data_type: &str,
+    document_id: &str,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling {} document '{}' for '{}'",
+        data_type, document_id, person.person.name
+    );
+
+    let client = &person.client;
+    let person_name = person.person.name.as_str();
+
+    let oura_data = match data_type {
+        "daily_activity" => {
+            let documents: [OuraActivityDocument; 1] =
+                [client.get_document(data_type, document_id).await?];
+
+            parse_activity_data(person_name, &documents)
+                .chain(parse_activity_class_data(person_name, &documents))
+                .chain(parse_met_data(person_name, &documents))
+                .collect()
+        }
+        "daily_cardiovascular_age" => {
+            let document: OuraDailyCardiovascularAgeDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_cardiovascular_age(person_name)
+                .map_or_else(OuraData::from, OuraData::CardiovascularAge)]
+        }
+        "daily_readiness" => {
+            let document: OuraDailyReadinessDocument =
+                client.get_document(data_type, document_id).await?;
+
+            match (document.score, document.day.try_parse_oura_date()) {
+                (None, Ok(day)) => poll_day_readiness_data(person, day).await?,
+                _ => vec![document
+                    .try_to_readiness(person_name)
+                    .map_or_else(OuraData::from, OuraData::Readiness)],
+            }
+        }
+        "daily_resilience" => {
+            let document: OuraDailyResilienceDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_resilience(person_name)
+                .map_or_else(OuraData::from, OuraData::Resilience)]
+        }
+        "daily_sleep" => {
+            let document: OuraDailySleepDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_daily_sleep_score(person_name)
+                .map_or_else(OuraData::from, OuraData::DailySleepScore)]
+        }
+        "daily_spo2" => {
+            let document: OuraDailySpO2Document =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_spo2(person_name)
+                .map_or_else(OuraData::from, OuraData::SpO2)]
+        }
+        "daily_stress" => {
+            let document: OuraDailyStressDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_stress(person_name)
+                .map_or_else(OuraData::from, OuraData::Stress)]
+        }
+        "enhanced_tag" => {
+            let documents: [OuraEnhancedTagDocument; 1] =
+                [client.get_document(data_type, document_id).await?];
+
+            parse_enhanced_tag_data(person_name, &documents).collect()
+        }
+        "rest_mode_period" => {
+            let document: OuraRestModePeriodDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_rest_mode_period(person_name)
+                .map_or_else(OuraData::from, OuraData::RestModePeriod)]
+        }
+        "session" => {
+            let documents: [OuraSessionDocument; 1] =
+                [client.get_document(data_type, document_id).await?];
+
+            parse_session_data(person_name, &documents)
+                .chain(parse_session_sample_data(person_name, &documents))
+                .collect()
+        }
+        "sleep" => {
+            let documents: Vec<OuraSleepDocument> =
+                vec![client.get_document(data_type, document_id).await?];
+
+            let mut oura_data: Vec<OuraData> = parse_heart_rate_data(person_name, &documents)
+                .chain(parse_hrv_data(person_name, &documents))
+                .chain(parse_sleep_data(person_name, &documents))
+                .chain(parse_sleep_phase_data(person_name, &documents))
+                .chain(parse_sleep_series_summary_data(person_name, &documents))
+                .chain(parse_sleep_movement_data(person_name, &documents))
+                .collect();
+
+            // The readiness of a night may only be available in its sleep document, which the
+            // polling falls back to. The day is polled like a polling round would poll it, so
+            // that a `daily_readiness` document still takes precedence.
+            let readiness_day = documents
+                .iter()
+                .filter(|document| document.sleep_type == "long_sleep")
+                .filter(|document| {
+                    matches!(&document.readiness, Some(readiness) if readiness.score.is_some())
+                })
+                .find_map(|document| document.day.try_parse_oura_date().ok());
+            if let Some(day) = readiness_day {
+                match poll_day_readiness_data(person, day).await {
+                    Ok(readiness_data) => oura_data.extend(readiness_data),
+                    Err(err) => oura_data.push(OuraData::from(err)),
+                }
+            }
+
+            oura_data
+        }
+        "sleep_time" => {
+            let document: OuraSleepTimeDocument =
+                client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_bedtime_recommendation(person_name)
+                .map_or_else(OuraData::from, OuraData::BedtimeRecommendation)]
+        }
+        "tag" => {
+            let documents: [OuraTagDocument; 1] =
+                [client.get_document(data_type, document_id).await?];
+
+            parse_tag_data(person_name, &documents).collect()
+        }
+        "vO2_max" => {
+            let document: OuraVO2MaxDocument = client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_vo2_max(person_name)
+                .map_or_else(OuraData::from, OuraData::VO2Max)]
+        }
+        "workout" => {
+            let document: OuraWorkoutDocument = client.get_document(data_type, document_id).await?;
+
+            vec![document
+                .try_to_workout(person_name)
+                .map_or_else(OuraData::from, OuraData::Workout)]
+        }
+        _ => vec![OuraData::Error {
+            message: format!("Unsupported webhook data type: '{}'", data_type),
+        }],
+    };
+
+    Ok(oura_data)
+}
@@ -0,0 +1,229 @@
+use super::{OuraData, PollerPerson};
+use crate::oura_api::{OuraApiError, OuraWorkoutDocument};
+use crate::pollers::dates::TryOuraTimeStringParsing;
+use crate::pollers::errors::OuraPollingError;
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use log::info;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum WorkoutIntensity {
+    Easy,
+    Moderate,
+    Hard,
+}
+
+impl FromStr for WorkoutIntensity {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(WorkoutIntensity::Easy),
+            "moderate" => Ok(WorkoutIntensity::Moderate),
+            "hard" => Ok(WorkoutIntensity::Hard),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "WorkoutIntensity".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for WorkoutIntensity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorkoutIntensity::Easy => write!(f, "easy"),
+            WorkoutIntensity::Moderate => write!(f, "moderate"),
+            WorkoutIntensity::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WorkoutSource {
+    Manual,
+    Autodetected,
+    Confirmed,
+    WorkoutHeartRate,
+}
+
+impl FromStr for WorkoutSource {
+    type Err = OuraPollingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "manual" => Ok(WorkoutSource::Manual),
+            "autodetected" => Ok(WorkoutSource::Autodetected),
+            "confirmed" => Ok(WorkoutSource::Confirmed),
+            "workout_heart_rate" => Ok(WorkoutSource::WorkoutHeartRate),
+            _ => Err(OuraPollingError::UnknownEnumVariantError {
+                enum_name: "WorkoutSource".to_string(),
+                variant: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for WorkoutSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WorkoutSource::Manual => write!(f, "manual"),
+            WorkoutSource::Autodetected => write!(f, "autodetected"),
+            WorkoutSource::Confirmed => write!(f, "confirmed"),
+            WorkoutSource::WorkoutHeartRate => write!(f, "workout_heart_rate"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Workout {
+    pub id: String,
+    pub activity: String,
+    pub intensity: WorkoutIntensity,
+    pub calories: Option<f32>,
+    pub distance: Option<f32>,
+    pub label: Option<String>,
+    pub source: WorkoutSource,
+    pub start_datetime: DateTime<Utc>,
+    pub end_datetime: DateTime<Utc>,
+    pub person_name: String,
+}
+
+impl Workout {
+    pub fn duration_in_seconds(&self) -> i64 {
+        (self.end_datetime - self.start_datetime).num_seconds()
+    }
+}
+
+impl OuraWorkoutDocument {
+    pub fn try_to_workout(&self, person_name: &str) -> Result<Workout, OuraPollingError> {
+        Ok(Workout {
+            id: self.id.clone(),
+            activity: self.activity.clone(),
+            intensity: self.intensity.parse()?,
+            calories: self.calories,
+            distance: self.distance,
+            label: self.label.clone(),
+            source: self.source.parse()?,
+            start_datetime: self.start_datetime.try_parse_oura_timestamp()?,
+            end_datetime: self.end_datetime.try_parse_oura_timestamp()?,
+            person_name: person_name.to_owned(),
+        })
+    }
+}
+
+pub async fn poll_workout_data(
+    person: &PollerPerson<'_>,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Result<Vec<OuraData>, OuraApiError> {
+    info!(
+        "Polling workout data for '{}' from {} to {}",
+        person.person.name, start_time, end_time
+    );
+
+    let workout_data: Vec<OuraData> = person
+        .client
+        .get_workouts(start_time, end_time)
+        .map_ok(
+            |document| match document.try_to_workout(&person.person.name) {
+                Ok(workout) => OuraData::Workout(workout),
+                Err(parsing_error) => OuraData::from(parsing_error),
+            },
+        )
+        .try_collect()
+        .await?;
+
+    Ok(workout_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{WorkoutIntensity, WorkoutSource};
+    use crate::oura_api::OuraWorkoutDocument;
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn test_workout_intensity_from_str() {
+        assert_eq!(
+            "easy".parse::<WorkoutIntensity>().unwrap(),
+            WorkoutIntensity::Easy
+        );
+        assert_eq!(
+            "moderate".parse::<WorkoutIntensity>().unwrap(),
+            WorkoutIntensity::Moderate
+        );
+        assert_eq!(
+            "hard".parse::<WorkoutIntensity>().unwrap(),
+            WorkoutIntensity::Hard
+        );
+
+        let error = "extreme".parse::<WorkoutIntensity>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown WorkoutIntensity: 'extreme'");
+    }
+
+    #[test]
+    fn test_workout_source_from_str() {
+        assert_eq!(
+            "manual".parse::<WorkoutSource>().unwrap(),
+            WorkoutSource::Manual
+        );
+        assert_eq!(
+            "autodetected".parse::<WorkoutSource>().unwrap(),
+            WorkoutSource::Autodetected
+        );
+        assert_eq!(
+            "confirmed".parse::<WorkoutSource>().unwrap(),
+            WorkoutSource::Confirmed
+        );
+        assert_eq!(
+            "workout_heart_rate".parse::<WorkoutSource>().unwrap(),
+            WorkoutSource::WorkoutHeartRate
+        );
+
+        let error = "watch".parse::<WorkoutSource>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown WorkoutSource: 'watch'");
+    }
+
+    #[test]
+    fn test_try_to_workout() {
+        let workout_document = OuraWorkoutDocument {
+            id: "workout_id".to_owned(),
+            activity: "cycling".to_owned(),
+            calories: Some(320.5),
+            day: "2021-01-01".to_owned(),
+            distance: Some(12000.0),
+            end_datetime: "2021-01-01T18:45:00+02:00".to_owned(),
+            intensity: "moderate".to_owned(),
+            label: Some("Commute".to_owned()),
+            source: "manual".to_owned(),
+            start_datetime: "2021-01-01T18:00:00+02:00".to_owned(),
+        };
+
+        let workout = workout_document.try_to_workout("test_person").unwrap();
+
+        assert_eq!(workout.id, "workout_id");
+        assert_eq!(workout.activity, "cycling");
+        assert_eq!(workout.intensity, WorkoutIntensity::Moderate);
+        assert_eq!(workout.calories, Some(320.5));
+        assert_eq!(workout.distance, Some(12000.0));
+        assert_eq!(workout.label, Some("Commute".to_owned()));
+        assert_eq!(workout.source, WorkoutSource::Manual);
+        assert_eq!(
+            workout.start_datetime,
+            "2021-01-01T16:00:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(
+            workout.end_datetime,
+            "2021-01-01T16:45:00+00:00"
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        );
+        assert_eq!(workout.duration_in_seconds(), 2700);
+        assert_eq!(workout.person_name, "test_person");
+    }
+}
@@ -0,0 +1,655 @@
+mod receiver;
+mod subscriptions;
+
+use crate::config::Webhook;
+use crate::oura_api::{OuraWebhookClient, OuraWebhookEvent};
+use crate::pollers::{OuraData, Poller};
+use log::{debug, error, info, warn};
+use receiver::{handle_request, read_request, WebhookResponse};
+use reqwest::Url;
+use std::time::{Duration, Instant};
+use subscriptions::sync_subscriptions;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+pub const DEFAULT_LISTEN_ADDRESS: &str = "0.0.0.0:8086";
+
+/// Oura webhook subscriptions expire, so they are renewed well before that.
+const SUBSCRIPTION_RENEWAL_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const SUBSCRIPTION_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+/// Oura sends the whole notification at once, so a connection that has not sent a complete
+/// request by then is closed.
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Resolving the user ids requests the personal info of every person, so unknown user ids, e.g.
+/// of a stale subscription, are resolved again at most this often.
+const USER_ID_RESOLUTION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const PERSON_METADATA_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Invalid webhook callback URL '{0}': {1}")]
+    InvalidCallbackUrl(String, String),
+}
+
+/// Receives the webhook notifications of Oura instead of polling. Each notification is answered
+/// right away and the referenced document is fetched and sent to `tx` afterwards, so that a slow
+/// Oura API does not make the notifications time out.
+pub async fn receive_webhooks(
+    listener: TcpListener,
+    config: &Webhook,
+    webhook_client: &OuraWebhookClient<'_>,
+    poller: &Poller<'_>,
+    tx: UnboundedSender<Vec<OuraData>>,
+) -> Result<(), WebhookError> {
+    let callback_url = Url::parse(&config.callback_url).map_err(|err| {
+        WebhookError::InvalidCallbackUrl(config.callback_url.to_string(), err.to_string())
+    })?;
+    let (events_tx, events_rx) = unbounded_channel();
+
+    // The receiver has to be running before the subscriptions are created, because Oura verifies
+    // the callback URL while creating them.
+    tokio::join!(
+        serve(
+            listener,
+            callback_url.path(),
+            &config.verification_token,
+            &config.client_secret,
+            events_tx
+        ),
+        process_events(events_rx, poller, tx),
+        keep_subscriptions(webhook_client, config),
+    );
+
+    Ok(())
+}
+
+async fn serve(
+    listener: TcpListener,
+    callback_path: &str,
+    verification_token: &str,
+    client_secret: &str,
+    events_tx: UnboundedSender<OuraWebhookEvent>,
+) {
+    while !events_tx.is_closed() {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                warn!("Error accepting webhook connection: {}", err);
+                continue;
+            }
+        };
+
+        // Each connection is handled in its own task, so that a slow or idle client cannot hold
+        // up the notifications of Oura.
+        tokio::spawn(handle_connection(
+            stream,
+            callback_path.to_string(),
+            verification_token.to_string(),
+            client_secret.to_string(),
+            events_tx.clone(),
+        ));
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    callback_path: String,
+    verification_token: String,
+    client_secret: String,
+    events_tx: UnboundedSender<OuraWebhookEvent>,
+) {
+    let response = match tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(&mut stream)).await
+    {
+        Ok(Ok(Some(request))) => handle_request(
+            &request,
+            &callback_path,
+            &verification_token,
+            &client_secret,
+        ),
+        Ok(Ok(None)) => WebhookResponse::BadRequest(String::from("Incomplete request")),
+        Ok(Err(err)) => {
+            warn!("Error reading webhook request: {}", err);
+            return;
+        }
+        Err(_) => {
+            debug!("Closing webhook connection that did not send a request in time");
+            return;
+        }
+    };
+
+    if let Err(err) = stream
+        .write_all(response.to_http_response().as_bytes())
+        .await
+    {
+        warn!("Error writing webhook response: {}", err);
+    }
+
+    match response {
+        WebhookResponse::Event(event) => {
+            if let Err(err) = events_tx.send(event) {
+                error!("Error queueing webhook notification: {}", err);
+            }
+        }
+        WebhookResponse::Challenge(_) => info!("Answered webhook verification challenge"),
+        other => debug!("Rejected webhook request: {:?}", other),
+    }
+}
+
+async fn process_events(
+    mut events_rx: UnboundedReceiver<OuraWebhookEvent>,
+    poller: &Poller<'_>,
+    tx: UnboundedSender<Vec<OuraData>>,
+) {
+    let mut person_names_by_user_id = poller.get_person_names_by_user_id().await;
+    let mut resolved_at = Instant::now();
+    let mut person_metadata_polled_at: Option<Instant> = None;
+
+    while let Some(event) = events_rx.recv().await {
+        info!(
+            "Received webhook notification: {} {} '{}'",
+            event.event_type, event.data_type, event.object_id
+        );
+
+        if event.event_type == "delete" {
+            continue;
+        }
+
+        let user_id = event.user_id.as_deref().unwrap_or_default();
+        if !person_names_by_user_id.contains_key(user_id)
+            && resolved_at.elapsed() >= USER_ID_RESOLUTION_INTERVAL
+        {
+            // The user id of a person may not have been resolved yet, e.g. after a failed request.
+            person_names_by_user_id = poller.get_person_names_by_user_id().await;
+            resolved_at = Instant::now();
+        }
+
+        let person_name = match person_names_by_user_id.get(user_id) {
+            Some(person_name) => person_name,
+            None => {
+                warn!(
+                    "Ignoring webhook notification of unknown Oura user '{}'",
+                    user_id
+                );
+                continue;
+            }
+        };
+
+        // The person metadata is sent before the document so that its tags are known by the time
+        // the document gets exported.
+        let is_person_metadata_outdated = person_metadata_polled_at
+            .is_none_or(|polled_at| polled_at.elapsed() >= PERSON_METADATA_REFRESH_INTERVAL);
+        if is_person_metadata_outdated {
+            let person_metadata = poller.poll_all_person_metadata().await;
+            person_metadata_polled_at = Some(Instant::now());
+
+            if !person_metadata.is_empty() {
+                if let Err(e) = tx.send(person_metadata) {
+                    error!("Error sending data to channel: {}", e);
+                }
+            }
+        }
+
+        let data = poller
+            .poll_webhook_document(person_name, &event.data_type, &event.object_id)
+            .await;
+
+        if let Err(e) = tx.send(data) {
+            error!("Error sending data to channel: {}", e);
+        }
+    }
+}
+
+async fn keep_subscriptions(webhook_client: &OuraWebhookClient<'_>, config: &Webhook) {
+    loop {
+        let wait = match sync_subscriptions(webhook_client, config).await {
+            Ok(_) => SUBSCRIPTION_RENEWAL_INTERVAL,
+            Err(err) => {
+                error!("Error synchronizing webhook subscriptions: {}", err);
+                SUBSCRIPTION_RETRY_INTERVAL
+            }
+        };
+
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{process_events, serve};
+    use crate::config::{OuraApi, OuraPerson};
+    use crate::oura_api::OuraWebhookEvent;
+    use crate::pollers::{OuraData, Poller};
+    use chrono::Utc;
+    use hmac::{Hmac, Mac};
+    use mockito::{Matcher, Server};
+    use sha2::Sha256;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn oura_api_config(server: &Server) -> Option<OuraApi> {
+        let (host, port) = server.host_with_port().split_once(':').map_or_else(
+            || panic!("mockito server address should contain a port"),
+            |(host, port)| (host.to_string(), port.to_string()),
+        );
+
+        Some(OuraApi {
+            url: Some(format!("http://{}", host)),
+            port: Some(port),
+            proxy: None,
+            verbose_logging: None,
+            sandbox: None,
+            timeout_seconds: None,
+            retry: None,
+        })
+    }
+
+    fn persons() -> Vec<OuraPerson> {
+        vec![OuraPerson {
+            name: "person".to_string(),
+            access_token: Some("token".to_string()),
+            oauth2: None,
+        }]
+    }
+
+    async fn send_request(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        response
+    }
+
+    #[tokio::test]
+    async fn test_idle_connection_does_not_block_other_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (events_tx, _events_rx) = unbounded_channel();
+
+        let requests = async {
+            let _idle_connection = TcpStream::connect(address).await.unwrap();
+
+            send_request(
+                address,
+                "GET /webhook?verification_token=secret&challenge=abc HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            )
+            .await
+        };
+
+        let challenge = tokio::select! {
+            challenge = tokio::time::timeout(Duration::from_secs(5), requests) => challenge.unwrap(),
+            _ = serve(listener, "/webhook", "secret", "client-secret", events_tx) => panic!("Webhook receiver stopped"),
+        };
+
+        assert!(challenge.starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_user_ids_are_not_resolved_on_every_notification() {
+        let mut server = Server::new_async().await;
+        let personal_info = server
+            .mock("GET", "/v2/usercollection/personal_info")
+            .with_body(r#"{"id": "user-id"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let oura_api_config = oura_api_config(&server);
+        let persons = persons();
+        let poller = Poller::initialize_with_persons(&persons, &oura_api_config, false).unwrap();
+        let (events_tx, events_rx) = unbounded_channel();
+        let (tx, mut rx) = unbounded_channel();
+
+        for _ in 0..3 {
+            events_tx
+                .send(OuraWebhookEvent {
+                    event_type: "create".to_string(),
+                    data_type: "workout".to_string(),
+                    object_id: "workout-id".to_string(),
+                    event_time: None,
+                    user_id: Some("stale-user-id".to_string()),
+                })
+                .unwrap();
+        }
+        drop(events_tx);
+
+        process_events(events_rx, &poller, tx).await;
+
+        personal_info.assert_async().await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_person_metadata_is_sent_before_the_documents() {
+        let mut server = Server::new_async().await;
+        let personal_info = server
+            .mock("GET", "/v2/usercollection/personal_info")
+            .with_body(r#"{"id": "user-id", "age": 30}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let ring_configuration = server
+            .mock("GET", "/v2/usercollection/ring_configuration")
+            .match_query(Matcher::Any)
+            .with_body(r#"{"data": [], "next_token": null}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let tag = server
+            .mock("GET", "/v2/usercollection/tag/tag-id")
+            .with_body(
+                r#"{
+                    "id": "tag-id",
+                    "day": "2021-01-01",
+                    "text": null,
+                    "timestamp": "2021-01-01T12:00:00+00:00",
+                    "tags": ["tag_generic_nocaffeine"]
+                }"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let oura_api_config = oura_api_config(&server);
+        let persons = persons();
+        let poller = Poller::initialize_with_persons(&persons, &oura_api_config, true).unwrap();
+        let (events_tx, events_rx) = unbounded_channel();
+        let (tx, mut rx) = unbounded_channel();
+
+        for _ in 0..2 {
+            events_tx
+                .send(OuraWebhookEvent {
+                    event_type: "update".to_string(),
+                    data_type: "tag".to_string(),
+                    object_id: "tag-id".to_string(),
+                    event_time: None,
+                    user_id: Some("user-id".to_string()),
+                })
+                .unwrap();
+        }
+        drop(events_tx);
+
+        process_events(events_rx, &poller, tx).await;
+
+        personal_info.assert_async().await;
+        ring_configuration.assert_async().await;
+        tag.assert_async().await;
+        match &rx.recv().await.unwrap()[..] {
+            [OuraData::PersonMetadata(person_metadata)] => {
+                assert_eq!(person_metadata.person_name, "person");
+            }
+            other => panic!("Expected person metadata, got {:?}", other),
+        }
+        for _ in 0..2 {
+            assert!(matches!(
+                &rx.recv().await.unwrap()[..],
+                [OuraData::Tag(_), ..]
+            ));
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    const SLEEP_DOCUMENT: &str = r#"{
+        "id": "sleep-id",
+        "awake_time": 0,
+        "bedtime_end": "2021-01-01T07:00:00+00:00",
+        "bedtime_start": "2020-12-31T23:00:00+00:00",
+        "day": "2021-01-01",
+        "hrv": {"interval": 300.0, "items": [], "timestamp": "2020-12-31T23:00:00+00:00"},
+        "low_battery_alert": false,
+        "movement_30_sec": "",
+        "period": 0,
+        "readiness": {
+            "contributors": {
+                "activity_balance": 1,
+                "body_temperature": 2,
+                "hrv_balance": 3,
+                "previous_day_activity": 4,
+                "previous_night": 5,
+                "recovery_index": 6,
+                "resting_heart_rate": 7,
+                "sleep_balance": 8
+            },
+            "score": 75,
+            "temperature_deviation": null,
+            "temperature_trend_deviation": null
+        },
+        "time_in_bed": 28800,
+        "type": "long_sleep"
+    }"#;
+
+    fn daily_readiness_document(score: Option<u8>) -> String {
+        format!(
+            r#"{{
+                "id": "readiness-id",
+                "contributors": {{}},
+                "day": "2021-01-01",
+                "score": {},
+                "temperature_deviation": null,
+                "temperature_trend_deviation": null,
+                "timestamp": "2021-01-01T00:00:00+00:00"
+            }}"#,
+            score.map_or("null".to_string(), |score| score.to_string())
+        )
+    }
+
+    async fn process_event(server: &Server, data_type: &str, object_id: &str) -> Vec<OuraData> {
+        let oura_api_config = oura_api_config(server);
+        let persons = persons();
+        let poller = Poller::initialize_with_persons(&persons, &oura_api_config, false).unwrap();
+        let (events_tx, events_rx) = unbounded_channel();
+        let (tx, mut rx) = unbounded_channel();
+
+        events_tx
+            .send(OuraWebhookEvent {
+                event_type: "create".to_string(),
+                data_type: data_type.to_string(),
+                object_id: object_id.to_string(),
+                event_time: None,
+                user_id: Some("user-id".to_string()),
+            })
+            .unwrap();
+        drop(events_tx);
+
+        process_events(events_rx, &poller, tx).await;
+
+        rx.recv().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unscored_daily_readiness_falls_back_to_the_sleep_readiness() {
+        let mut server = Server::new_async().await;
+        let _personal_info = server
+            .mock("GET", "/v2/usercollection/personal_info")
+            .with_body(r#"{"id": "user-id"}"#)
+            .create_async()
+            .await;
+        let _readiness_document = server
+            .mock("GET", "/v2/usercollection/daily_readiness/readiness-id")
+            .with_body(daily_readiness_document(None))
+            .create_async()
+            .await;
+        let _daily_readiness = server
+            .mock("GET", "/v2/usercollection/daily_readiness")
+            .match_query(Matcher::Any)
+            .with_body(format!(
+                r#"{{"data": [{}], "next_token": null}}"#,
+                daily_readiness_document(None)
+            ))
+            .create_async()
+            .await;
+        let sleep = server
+            .mock("GET", "/v2/usercollection/sleep")
+            .match_query(Matcher::Any)
+            .with_body(format!(
+                r#"{{"data": [{}], "next_token": null}}"#,
+                SLEEP_DOCUMENT
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let data = process_event(&server, "daily_readiness", "readiness-id").await;
+
+        sleep.assert_async().await;
+        match &data[..] {
+            [OuraData::Readiness(readiness)] => {
+                assert_eq!(readiness.id, "sleep-id");
+                assert_eq!(readiness.score, 75);
+            }
+            other => panic!("Expected the sleep readiness, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sleep_notification_prefers_the_daily_readiness() {
+        let mut server = Server::new_async().await;
+        let _personal_info = server
+            .mock("GET", "/v2/usercollection/personal_info")
+            .with_body(r#"{"id": "user-id"}"#)
+            .create_async()
+            .await;
+        let _sleep_document = server
+            .mock("GET", "/v2/usercollection/sleep/sleep-id")
+            .with_body(SLEEP_DOCUMENT)
+            .create_async()
+            .await;
+        let daily_readiness = server
+            .mock("GET", "/v2/usercollection/daily_readiness")
+            .match_query(Matcher::Any)
+            .with_body(format!(
+                r#"{{"data": [{}], "next_token": null}}"#,
+                daily_readiness_document(Some(80))
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let sleep = server
+            .mock("GET", "/v2/usercollection/sleep")
+            .match_query(Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let data = process_event(&server, "sleep", "sleep-id").await;
+
+        daily_readiness.assert_async().await;
+        sleep.assert_async().await;
+        let readiness: Vec<_> = data
+            .iter()
+            .filter_map(|data| match data {
+                OuraData::Readiness(readiness) => Some(readiness),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(readiness.len(), 1);
+        assert_eq!(readiness[0].id, "readiness-id");
+        assert_eq!(readiness[0].score, 80);
+    }
+
+    #[tokio::test]
+    async fn test_receive_webhook_notifications() {
+        let mut server = Server::new_async().await;
+        let personal_info = server
+            .mock("GET", "/v2/usercollection/personal_info")
+            .with_body(r#"{"id": "user-id"}"#)
+            .create_async()
+            .await;
+        let workout = server
+            .mock("GET", "/v2/usercollection/workout/workout-id")
+            .match_header("authorization", "Bearer token")
+            .with_body(
+                r#"{
+                    "id": "workout-id",
+                    "activity": "cycling",
+                    "calories": 300.0,
+                    "day": "2021-01-01",
+                    "distance": 10000.0,
+                    "end_datetime": "2021-01-01T13:00:00+00:00",
+                    "intensity": "moderate",
+                    "label": null,
+                    "source": "manual",
+                    "start_datetime": "2021-01-01T12:00:00+00:00"
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let oura_api_config = oura_api_config(&server);
+        let persons = persons();
+        let poller = Poller::initialize_with_persons(&persons, &oura_api_config, false).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (events_tx, events_rx) = unbounded_channel();
+        let (tx, mut rx) = unbounded_channel();
+
+        let notifications = async {
+            let challenge = send_request(
+                address,
+                "GET /webhook?verification_token=secret&challenge=abc HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            )
+            .await;
+            assert!(challenge.starts_with("HTTP/1.1 200 OK"));
+            assert!(challenge.ends_with(r#"{"challenge":"abc"}"#));
+
+            let invalid_token = send_request(
+                address,
+                "GET /webhook?verification_token=other&challenge=abc HTTP/1.1\r\nHost: localhost\r\n\r\n",
+            )
+            .await;
+            assert!(invalid_token.starts_with("HTTP/1.1 401 Unauthorized"));
+
+            let body = r#"{
+                "event_type": "create",
+                "data_type": "workout",
+                "object_id": "workout-id",
+                "event_time": "2021-01-01T13:05:00+00:00",
+                "user_id": "user-id"
+            }"#;
+            let timestamp = Utc::now().timestamp().to_string();
+            let mut mac = Hmac::<Sha256>::new_from_slice(b"client-secret").unwrap();
+            mac.update(timestamp.as_bytes());
+            mac.update(body.as_bytes());
+            let signature = hex::encode_upper(mac.finalize().into_bytes());
+            let notification = send_request(
+                address,
+                &format!(
+                    "POST /webhook HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nX-Oura-Timestamp: {}\r\nX-Oura-Signature: {}\r\nContent-Length: {}\r\n\r\n{}",
+                    timestamp,
+                    signature,
+                    body.len(),
+                    body
+                ),
+            )
+            .await;
+            assert!(notification.starts_with("HTTP/1.1 200 OK"));
+
+            rx.recv().await.unwrap()
+        };
+
+        let data = tokio::select! {
+            data = notifications => data,
+            _ = serve(listener, "/webhook", "secret", "client-secret", events_tx) => panic!("Webhook receiver stopped"),
+            _ = process_events(events_rx, &poller, tx) => panic!("Webhook event processing stopped"),
+        };
+
+        personal_info.assert_async().await;
+        workout.assert_async().await;
+        assert_eq!(data.len(), 1);
+        match &data[0] {
+            OuraData::Workout(workout) => {
+                assert_eq!(workout.id, "workout-id");
+                assert_eq!(workout.person_name, "person");
+            }
+            other => panic!("Expected a workout, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::oura_api::OuraWebhookEvent;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use sha2::Sha256;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Webhook notifications are small JSON documents, anything larger is not read.
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+const SIGNATURE_HEADER: &str = "x-oura-signature";
+const TIMESTAMP_HEADER: &str = "x-oura-timestamp";
+
+/// Notifications whose timestamp is further away from the current time are rejected, so that a
+/// captured notification cannot be replayed later.
+const MAX_TIMESTAMP_DIFFERENCE_IN_SECONDS: i64 = 5 * 60;
+
+#[derive(Debug, PartialEq)]
+pub struct WebhookRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    /// The header names are in lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug)]
+pub enum WebhookResponse {
+    /// Oura verifies the callback URL with a challenge when a subscription is created.
+    Challenge(String),
+    Event(OuraWebhookEvent),
+    Unauthorized(String),
+    NotFound,
+    BadRequest(String),
+}
+
+impl WebhookResponse {
+    pub fn to_http_response(&self) -> String {
+        let (status, content_type, body) = match self {
+            WebhookResponse::Challenge(challenge) => (
+                "200 OK",
+                "application/json",
+                serde_json::json!({ "challenge": challenge }).to_string(),
+            ),
+            WebhookResponse::Event(_) => ("200 OK", "text/plain", String::from("OK")),
+            WebhookResponse::Unauthorized(error) => {
+                ("401 Unauthorized", "text/plain", error.clone())
+            }
+            WebhookResponse::NotFound => ("404 Not Found", "text/plain", String::from("Not found")),
+            WebhookResponse::BadRequest(error) => ("400 Bad Request", "text/plain", error.clone()),
+        };
+
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        )
+    }
+}
+
+fn find_header_end(buffer: &[u8]) -> Option<usize> {
+    buffer
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|position| position + 4)
+}
+
+fn parse_content_length(head: &str) -> usize {
+    head.lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Reads a single HTTP request, including the body announced by its `Content-Length` header.
+/// Returns `None` when the connection is closed before a complete request has been received.
+pub async fn read_request<R>(stream: &mut R) -> std::io::Result<Option<WebhookRequest>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = Vec::new();
+    let mut chunk = vec![0; 8192];
+
+    loop {
+        if let Some(header_end) = find_header_end(&buffer) {
+            let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+            let request_size = header_end + parse_content_length(&head);
+
+            if buffer.len() >= request_size {
+                return Ok(parse_request(&head, &buffer[header_end..request_size]));
+            }
+        }
+
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Ok(None);
+        }
+
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn parse_request(head: &str, body: &[u8]) -> Option<WebhookRequest> {
+    let mut request_line = head.lines().next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let url = Url::parse(&format!("http://localhost{}", request_line.next()?)).ok()?;
+
+    let headers = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    Some(WebhookRequest {
+        method,
+        path: url.path().to_string(),
+        query: url.query_pairs().into_owned().collect(),
+        headers,
+        body: String::from_utf8_lossy(body).to_string(),
+    })
+}
+
+fn find_parameter<'b>(parameters: &'b [(String, String)], name: &str) -> Option<&'b str> {
+    parameters
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Oura signs each notification with an HMAC-SHA256 of the timestamp header and the body, keyed
+/// with the client secret of the application. The timestamp is in seconds since the epoch.
+fn has_valid_signature(request: &WebhookRequest, client_secret: &str, now: i64) -> bool {
+    let (signature, timestamp) = match (
+        find_parameter(&request.headers, SIGNATURE_HEADER),
+        find_parameter(&request.headers, TIMESTAMP_HEADER),
+    ) {
+        (Some(signature), Some(timestamp)) => (signature, timestamp),
+        _ => return false,
+    };
+    match timestamp.parse::<i64>() {
+        Ok(seconds) if (now - seconds).abs() <= MAX_TIMESTAMP_DIFFERENCE_IN_SECONDS => (),
+        _ => return false,
+    }
+    let signature = match hex::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac = match Hmac::<Sha256>::new_from_slice(client_secret.as_bytes()) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+
+    mac.update(timestamp.as_bytes());
+    mac.update(request.body.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+pub fn handle_request(
+    request: &WebhookRequest,
+    callback_path: &str,
+    verification_token: &str,
+    client_secret: &str,
+) -> WebhookResponse {
+    if request.path != callback_path {
+        return WebhookResponse::NotFound;
+    }
+
+    match request.method.as_str() {
+        "GET" => {
+            if find_parameter(&request.query, "verification_token") != Some(verification_token) {
+                return WebhookResponse::Unauthorized(String::from("Invalid verification token"));
+            }
+
+            match find_parameter(&request.query, "challenge") {
+                Some(challenge) => WebhookResponse::Challenge(challenge.to_string()),
+                None => WebhookResponse::BadRequest(String::from("Challenge is missing")),
+            }
+        }
+        "POST" if !has_valid_signature(request, client_secret, Utc::now().timestamp()) => {
+            WebhookResponse::Unauthorized(String::from("Invalid signature"))
+        }
+        "POST" => match serde_json::from_str::<OuraWebhookEvent>(&request.body) {
+            Ok(event) => WebhookResponse::Event(event),
+            Err(err) => {
+                WebhookResponse::BadRequest(format!("Invalid webhook notification: {}", err))
+            }
+        },
+        _ => WebhookResponse::NotFound,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{handle_request, read_request, WebhookRequest, WebhookResponse};
+    use chrono::Utc;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    fn sign(client_secret: &str, timestamp: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(client_secret.as_bytes()).unwrap();
+        mac.update(timestamp.as_bytes());
+        mac.update(body.as_bytes());
+
+        hex::encode_upper(mac.finalize().into_bytes())
+    }
+
+    fn notification(headers: Vec<(&str, String)>) -> WebhookRequest {
+        WebhookRequest {
+            method: "POST".to_owned(),
+            path: "/webhook".to_owned(),
+            query: vec![],
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value))
+                .collect(),
+            body: r#"{"event_type": "create", "data_type": "sleep", "object_id": "sleep-id"}"#
+                .to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_request_with_body() {
+        let body = r#"{"event_type": "create"}"#;
+        let raw_request = format!(
+            "POST /webhook?source=oura HTTP/1.1\r\nHost: localhost\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        let request = read_request(&mut raw_request.as_bytes())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            request,
+            WebhookRequest {
+                method: "POST".to_owned(),
+                path: "/webhook".to_owned(),
+                query: vec![("source".to_owned(), "oura".to_owned())],
+                headers: vec![
+                    ("host".to_owned(), "localhost".to_owned()),
+                    ("content-length".to_owned(), body.len().to_string()),
+                ],
+                body: body.to_owned(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_request_with_truncated_body() {
+        let raw_request = "POST /webhook HTTP/1.1\r\nContent-Length: 100\r\n\r\n{}";
+
+        let request = read_request(&mut raw_request.as_bytes()).await.unwrap();
+
+        assert_eq!(request, None);
+    }
+
+    #[test]
+    fn test_handle_verification_challenge() {
+        let request = WebhookRequest {
+            method: "GET".to_owned(),
+            path: "/webhook".to_owned(),
+            query: vec![
+                ("verification_token".to_owned(), "secret".to_owned()),
+                ("challenge".to_owned(), "abc".to_owned()),
+            ],
+            headers: vec![],
+            body: String::new(),
+        };
+
+        match handle_request(&request, "/webhook", "secret", "client-secret") {
+            WebhookResponse::Challenge(challenge) => assert_eq!(challenge, "abc"),
+            other => panic!("Expected a challenge response, got {:?}", other),
+        }
+        assert!(matches!(
+            handle_request(&request, "/webhook", "other", "client-secret"),
+            WebhookResponse::Unauthorized(_)
+        ));
+        assert!(matches!(
+            handle_request(&request, "/other", "secret", "client-secret"),
+            WebhookResponse::NotFound
+        ));
+    }
+
+    #[test]
+    fn test_handle_signed_notification() {
+        let timestamp = Utc::now().timestamp().to_string();
+        let mut request = notification(vec![("x-oura-timestamp", timestamp.clone())]);
+        let signature = sign("client-secret", &timestamp, &request.body);
+        request
+            .headers
+            .push(("x-oura-signature".to_owned(), signature));
+
+        match handle_request(&request, "/webhook", "secret", "client-secret") {
+            WebhookResponse::Event(event) => assert_eq!(event.object_id, "sleep-id"),
+            other => panic!("Expected an event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reject_notification_with_missing_or_invalid_signature() {
+        let now = Utc::now().timestamp();
+        let timestamp = now.to_string();
+        let body = notification(vec![]).body;
+
+        let unsigned = notification(vec![("x-oura-timestamp", timestamp.clone())]);
+        let signed_with_other_secret = notification(vec![
+            ("x-oura-timestamp", timestamp.clone()),
+            ("x-oura-signature", sign("other-secret", &timestamp, &body)),
+        ]);
+        let signed_with_other_timestamp = notification(vec![
+            ("x-oura-timestamp", (now + 1).to_string()),
+            ("x-oura-signature", sign("client-secret", &timestamp, &body)),
+        ]);
+        let stale_timestamp = (now - 10 * 60).to_string();
+        let replayed = notification(vec![
+            ("x-oura-timestamp", stale_timestamp.clone()),
+            (
+                "x-oura-signature",
+                sign("client-secret", &stale_timestamp, &body),
+            ),
+        ]);
+        let malformed_signature = notification(vec![
+            ("x-oura-timestamp", timestamp.clone()),
+            ("x-oura-signature", "not-hex".to_owned()),
+        ]);
+
+        for request in [
+            unsigned,
+            signed_with_other_secret,
+            signed_with_other_timestamp,
+            replayed,
+            malformed_signature,
+        ] {
+            let response = handle_request(&request, "/webhook", "secret", "client-secret");
+
+            assert!(matches!(response, WebhookResponse::Unauthorized(_)));
+            assert!(response
+                .to_http_response()
+                .starts_with("HTTP/1.1 401 Unauthorized"));
+        }
+    }
+}
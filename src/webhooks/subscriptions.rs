@@ -0,0 +1,138 @@
+use crate::config::Webhook;
+use crate::oura_api::{OuraApiError, OuraWebhookClient, OuraWebhookSubscription};
+use crate::pollers::WEBHOOK_DATA_TYPES;
+use log::info;
+
+/// Deleted documents are not removed from the exporters, so only creations and updates are
+/// subscribed to.
+const EVENT_TYPES: &[&str] = &["create", "update"];
+
+#[derive(Debug, Default, PartialEq)]
+struct SubscriptionChanges<'a> {
+    create: Vec<(&'a str, &'a str)>,
+    renew: Vec<&'a str>,
+    delete: Vec<&'a str>,
+}
+
+/// Compares the existing subscriptions of the callback URL with the wanted ones. Existing
+/// subscriptions are renewed, because they expire, and subscriptions of data types that are no
+/// longer wanted are deleted. Subscriptions of other callback URLs are left alone.
+fn plan_subscription_changes<'a>(
+    existing_subscriptions: &'a [OuraWebhookSubscription],
+    callback_url: &str,
+    data_types: &[&'a str],
+) -> SubscriptionChanges<'a> {
+    let mut changes = SubscriptionChanges::default();
+    let own_subscriptions: Vec<&OuraWebhookSubscription> = existing_subscriptions
+        .iter()
+        .filter(|subscription| subscription.callback_url == callback_url)
+        .collect();
+
+    for subscription in &own_subscriptions {
+        let wanted = EVENT_TYPES.contains(&subscription.event_type.as_str())
+            && data_types.contains(&subscription.data_type.as_str());
+
+        if wanted {
+            changes.renew.push(&subscription.id);
+        } else {
+            changes.delete.push(&subscription.id);
+        }
+    }
+
+    for data_type in data_types {
+        for event_type in EVENT_TYPES {
+            let exists = own_subscriptions.iter().any(|subscription| {
+                subscription.event_type == *event_type && subscription.data_type == *data_type
+            });
+
+            if !exists {
+                changes.create.push((event_type, data_type));
+            }
+        }
+    }
+
+    changes
+}
+
+pub async fn sync_subscriptions(
+    client: &OuraWebhookClient<'_>,
+    config: &Webhook,
+) -> Result<(), OuraApiError> {
+    let data_types: Vec<&str> = match &config.data_types {
+        Some(data_types) => data_types.iter().map(String::as_str).collect(),
+        None => WEBHOOK_DATA_TYPES.to_vec(),
+    };
+    let existing_subscriptions = client.list_subscriptions().await?;
+    let changes =
+        plan_subscription_changes(&existing_subscriptions, &config.callback_url, &data_types);
+
+    for subscription_id in changes.delete {
+        client.delete_subscription(subscription_id).await?;
+        info!("Deleted webhook subscription '{}'", subscription_id);
+    }
+
+    for subscription_id in changes.renew {
+        client.renew_subscription(subscription_id).await?;
+        info!("Renewed webhook subscription '{}'", subscription_id);
+    }
+
+    for (event_type, data_type) in changes.create {
+        let subscription = client
+            .create_subscription(
+                &config.callback_url,
+                &config.verification_token,
+                event_type,
+                data_type,
+            )
+            .await?;
+        info!(
+            "Created webhook subscription '{}' for {} events of {}",
+            subscription.id, event_type, data_type
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{plan_subscription_changes, SubscriptionChanges};
+    use crate::oura_api::OuraWebhookSubscription;
+
+    fn subscription(
+        id: &str,
+        callback_url: &str,
+        event_type: &str,
+        data_type: &str,
+    ) -> OuraWebhookSubscription {
+        OuraWebhookSubscription {
+            id: id.to_owned(),
+            callback_url: callback_url.to_owned(),
+            event_type: event_type.to_owned(),
+            data_type: data_type.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_subscription_changes() {
+        let callback_url = "https://exporter.example.com/webhook";
+        let existing_subscriptions = vec![
+            subscription("1", callback_url, "create", "workout"),
+            subscription("2", callback_url, "create", "session"),
+            subscription("3", "https://other.example.com/webhook", "create", "tag"),
+        ];
+
+        let changes =
+            plan_subscription_changes(&existing_subscriptions, callback_url, &["workout", "tag"]);
+
+        assert_eq!(
+            changes,
+            SubscriptionChanges {
+                create: vec![("update", "workout"), ("create", "tag"), ("update", "tag")],
+                renew: vec!["1"],
+                delete: vec!["2"],
+            }
+        );
+    }
+}